@@ -1,10 +1,66 @@
+pub const DRIVER_OUTPUT_CONTROL: u8 = 0x01;
+pub const SET_GATE_VOLTAGE: u8 = 0x03;
+pub const SET_SOURCE_VOLTAGE: u8 = 0x04;
+pub const SET_GATE_SCAN_START: u8 = 0x0f;
+pub const DEEP_SLEEP_MODE: u8 = 0x10;
 pub const SET_DATA_ENTRY_MODE: u8 = 0x11;
 pub const RESET: u8 = 0x12;
 pub const SET_TEMPERATURE_SENSOR: u8 = 0x18;
+pub const READ_TEMPERATURE: u8 = 0x1b;
 pub const REFRESH_PANEL: u8 = 0x20;
 pub const SET_UPDATE_SEQUENCE: u8 = 0x22;
 pub const WRITE_RAM: u8 = 0x24;
+pub const WRITE_RAM_RED: u8 = 0x26;
+pub const READ_RAM: u8 = 0x27;
+pub const VCOM_SENSE: u8 = 0x28;
+pub const SET_VCOM_SENSE_DURATION: u8 = 0x29;
+pub const WRITE_VCOM: u8 = 0x2c;
+pub const READ_DISPLAY_OPTION: u8 = 0x2d;
+pub const READ_STATUS: u8 = 0x2f;
+pub const WRITE_LUT: u8 = 0x32;
+pub const SET_DUMMY_LINE_PERIOD: u8 = 0x3a;
+pub const SET_GATE_LINE_WIDTH: u8 = 0x3b;
+pub const SET_BORDER_WAVEFORM: u8 = 0x3c;
+pub const SET_RAM_READ_OPTION: u8 = 0x41;
 pub const SET_RAM_X: u8 = 0x44;
 pub const SET_RAM_Y: u8 = 0x45;
 pub const SET_X_POINTER: u8 = 0x4e;
 pub const SET_Y_POINTER: u8 = 0x4f;
+pub const NOP: u8 = 0x7f;
+
+/// Readable name of a standard SSD1681 command, for traces.
+#[cfg(feature = "trace")]
+pub(crate) fn name(command: u8) -> &'static str {
+    match command {
+        DRIVER_OUTPUT_CONTROL => "DRIVER_OUTPUT_CONTROL",
+        SET_GATE_VOLTAGE => "SET_GATE_VOLTAGE",
+        SET_SOURCE_VOLTAGE => "SET_SOURCE_VOLTAGE",
+        SET_GATE_SCAN_START => "SET_GATE_SCAN_START",
+        DEEP_SLEEP_MODE => "DEEP_SLEEP_MODE",
+        SET_DATA_ENTRY_MODE => "SET_DATA_ENTRY_MODE",
+        RESET => "RESET",
+        SET_TEMPERATURE_SENSOR => "SET_TEMPERATURE_SENSOR",
+        READ_TEMPERATURE => "READ_TEMPERATURE",
+        REFRESH_PANEL => "REFRESH_PANEL",
+        SET_UPDATE_SEQUENCE => "SET_UPDATE_SEQUENCE",
+        WRITE_RAM => "WRITE_RAM",
+        WRITE_RAM_RED => "WRITE_RAM_RED",
+        READ_RAM => "READ_RAM",
+        VCOM_SENSE => "VCOM_SENSE",
+        SET_VCOM_SENSE_DURATION => "SET_VCOM_SENSE_DURATION",
+        WRITE_VCOM => "WRITE_VCOM",
+        READ_DISPLAY_OPTION => "READ_DISPLAY_OPTION",
+        READ_STATUS => "READ_STATUS",
+        WRITE_LUT => "WRITE_LUT",
+        SET_DUMMY_LINE_PERIOD => "SET_DUMMY_LINE_PERIOD",
+        SET_GATE_LINE_WIDTH => "SET_GATE_LINE_WIDTH",
+        SET_BORDER_WAVEFORM => "SET_BORDER_WAVEFORM",
+        SET_RAM_READ_OPTION => "SET_RAM_READ_OPTION",
+        SET_RAM_X => "SET_RAM_X",
+        SET_RAM_Y => "SET_RAM_Y",
+        SET_X_POINTER => "SET_X_POINTER",
+        SET_Y_POINTER => "SET_Y_POINTER",
+        NOP => "NOP",
+        _ => "UNKNOWN",
+    }
+}
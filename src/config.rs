@@ -1,13 +1,266 @@
+use crate::commands::{READ_RAM, REFRESH_PANEL, SET_UPDATE_SEQUENCE, WRITE_RAM, WRITE_RAM_RED};
+
+/// Driver configuration.
+///
+/// `X` and `Y` are the visible resolution of the panel. On modules whose visible area is smaller
+/// than the controller RAM, set `visible_x_offset`/`visible_y_offset` to where that area starts
+/// in RAM.
 pub struct Ssd1681Config<const X: usize, const Y: usize> {
     pub busy_settle_ms: u32,
+    /// How to tell when the controller has finished an operation.
+    pub busy_strategy: BusyStrategy,
+    /// Fail with [`DisplayError::BusyTimeout`] if BUSY stays high for longer than this; `None`
+    /// waits indefinitely.
+    ///
+    /// [`DisplayError::BusyTimeout`]: crate::errors::DisplayError::BusyTimeout
+    pub busy_timeout_ms: Option<u32>,
+    /// Treat a refresh as finished only after BUSY has been seen going high and then low again,
+    /// for modules that hold BUSY low and only pulse it high briefly. Pair it with
+    /// [`BusyStrategy::HardwarePin`], as polling can miss a short pulse.
+    pub wait_for_busy_cycle: bool,
+    /// How many times a wait for BUSY is restarted after reading the pin fails, for GPIO drivers
+    /// that return spurious errors. A pin stuck at one level is caught by `busy_timeout_ms`
+    /// instead, which is not retried.
+    pub busy_read_retries: u8,
+    /// How many times init is retried after a BUSY timeout, waiting `init_backoff_base_ms` and
+    /// then twice as long before each further attempt. Pin and bus failures aren't retried.
+    pub init_max_retries: u8,
+    /// Wait before the first init retry, in milliseconds; doubles for each further one.
+    pub init_backoff_base_ms: u32,
     pub dc_settle_us: u32,
+    /// The delay provider only has millisecond resolution and rounds shorter delays up, which
+    /// would turn the settle after every DC change into a millisecond and make init take
+    /// seconds. Skips `dc_settle_us` while it is below a millisecond; the SPI transfer that
+    /// follows then has to give DC enough time on its own. `busy_settle_ms` is unaffected.
+    pub coarse_delay: bool,
+    /// Split large data writes into `write_chunk_size` byte transfers and yield to the executor
+    /// between them, so other tasks get a turn during a full frame write.
+    pub yield_during_write: bool,
+    pub write_chunk_size: usize,
+    /// Select the internal temperature sensor during init. Disable for dies without the sensor
+    /// wired, where the command never releases BUSY.
+    pub use_internal_temp_sensor: bool,
+    /// When a frame is drawn while a previous refresh is still running, wait for it to finish
+    /// instead of failing with [`DisplayError::RefreshInProgress`].
+    ///
+    /// [`DisplayError::RefreshInProgress`]: crate::errors::DisplayError::RefreshInProgress
+    pub wait_for_refresh: bool,
+    /// Dummy line period (0–127 line periods) applied during init; `None` keeps the OTP value.
+    pub dummy_line_period: Option<u8>,
+    /// Gate line width (0–15) applied during init; `None` keeps the OTP value.
+    pub gate_line_width: Option<u8>,
+    /// First gate line driven (gate scan start position, `0x0F`), for modules whose visible area
+    /// starts at a non-zero gate line. Use together with `visible_y_offset`.
+    pub gate_scan_start: u16,
+    /// Gate scan order; see [`ScanMode`].
+    pub scan_mode: ScanMode,
+    /// Scan the gate lines bottom to top (driver output control bit `TB`). Combined with
+    /// [`ScanMode::Interlaced`], the odd/even split follows the reversed order.
+    pub reverse_gate_scan: bool,
+    /// Send the bytes of every scanline last to first, and mirror the column window of partial
+    /// updates to match, for clone modules whose X address counter runs the other way so that
+    /// data entry mode alone leaves the image mirrored.
+    pub reverse_scanline_bytes: bool,
+    /// Border waveform control byte (command `0x3C`); `None` keeps the reset value.
+    pub border_waveform: Option<u8>,
+    /// Gate driving voltage byte (command `0x03`); `None` keeps the reset value.
+    pub gate_voltage: Option<u8>,
+    /// Source driving voltage bytes VSH1, VSH2 and VSL (command `0x04`); `None` keeps the reset
+    /// value.
+    pub source_voltage: Option<[u8; 3]>,
+    /// After a full refresh, also write the new frame to the red RAM bank, so the next refresh
+    /// finds the frame it transitions from there and the waveform drives only the pixels that
+    /// change. Costs a second RAM write per frame. Off by default, since on tri-color panels that
+    /// bank holds the red plane; only enable it on black/white panels.
+    ///
+    /// After [`DisplayDriver::start_frame`](crate::driver::DisplayDriver::start_frame) the bank
+    /// can only be caught up on the next frame, from the buffer given to
+    /// [`Ssd1681::retain_frames_in`](crate::driver::Ssd1681::retain_frames_in).
+    pub keep_shown_frame_in_red_ram: bool,
+    /// Refuse to refresh below this temperature in °C, failing with
+    /// [`DisplayError::TooCold`] instead; refreshing a panel colder than it is rated for can
+    /// leave permanent ghosting. Reads the temperature sensor before every refresh, so it needs an
+    /// SPI bus that can read from the panel. `None` disables the guard.
+    ///
+    /// [`DisplayError::TooCold`]: crate::errors::DisplayError::TooCold
+    pub min_refresh_temp_c: Option<i8>,
+    /// Promote every Nth partial update to a full refresh to clear accumulated ghosting; `0`
+    /// never promotes. The steps of
+    /// [`Ssd1681::transition`](crate::driver::Ssd1681::transition) don't count.
+    pub full_refresh_interval: u16,
+    /// Clear the controller RAM and run a full refresh during init, so untouched regions don't
+    /// show power-up garbage. Disable to keep the image the panel retained while unpowered.
+    pub clear_on_init: bool,
+    /// Column of controller RAM where the visible area starts. Must be a multiple of 8.
+    pub visible_x_offset: usize,
+    /// Gate line of controller RAM where the visible area starts.
+    pub visible_y_offset: usize,
+    /// Time for the panel supply to settle after the power pin switches it on.
+    pub power_on_delay_ms: u32,
+    /// Write the retained last frame back into RAM on wake from deep sleep. See
+    /// [`Ssd1681::wake`](crate::driver::Ssd1681::wake).
+    pub restore_ram_on_wake: bool,
+    /// Run [`Ssd1681::check_spi`](crate::driver::Ssd1681::check_spi) during init. Needs a bus
+    /// that can read from the panel.
+    pub check_spi_on_init: bool,
+    /// Switch the backlight on for each refresh and off again `backlight_hold_ms` after it
+    /// finishes. Does nothing without a backlight pin.
+    pub auto_backlight_on_refresh: bool,
+    pub backlight_hold_ms: u32,
+    /// Time every refresh by polling BUSY each millisecond, for
+    /// [`Ssd1681::last_refresh_duration_ms`](crate::driver::Ssd1681::last_refresh_duration_ms).
+    /// Needs a readable BUSY line, and replaces `busy_strategy` and `wait_for_busy_cycle` while
+    /// refreshing.
+    pub measure_refresh_duration: bool,
+    /// Called for every command sent, e.g. to forward it to `defmt::trace!` or `log::trace!`.
+    #[cfg(feature = "trace")]
+    pub trace: Option<fn(CommandTrace)>,
+    /// Opcodes for the RAM and refresh commands, for clone controllers that deviate from the
+    /// SSD1681.
+    pub commands: Commands,
+}
+
+/// Order in which the gate lines are scanned during a refresh (driver output control, `0x01`,
+/// bit `SM`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScanMode {
+    /// One gate line after the other.
+    #[default]
+    Sequential,
+    /// Even gate lines first, then the odd ones. A refresh takes as long but can look faster,
+    /// since the whole panel changes at once at half resolution; the image may shimmer while it
+    /// settles.
+    Interlaced,
+}
+
+/// How the driver waits for the controller to release BUSY.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum BusyStrategy {
+    /// Await the BUSY pin going low through its [`Wait`](embedded_hal_async::digital::Wait) impl.
+    /// Pins without one can be read in a loop instead by wrapping them in
+    /// [`PolledBusy`](crate::driver::PolledBusy), but [`BusyStrategy::Poll`] suits them better.
+    #[default]
+    HardwarePin,
+    /// Never read BUSY and wait this many milliseconds after every refresh (master activation)
+    /// and reset instead, for boards without the line wired; other commands only wait
+    /// `busy_settle_ms`. Size it for the slowest operation, a full refresh (about 2 s on the
+    /// SSD1681); a stub pin still has to be passed to the driver.
+    FixedDelay(u32),
+    /// Read the BUSY level every this many milliseconds (at least 1), for pins without edge
+    /// interrupts.
+    Poll(u32),
+}
+
+/// A command about to be sent, as passed to the `trace` hook.
+#[cfg(feature = "trace")]
+#[derive(Clone, Copy, Debug)]
+pub struct CommandTrace {
+    pub command: u8,
+    /// Name of the standard SSD1681 command with this opcode, or `"UNKNOWN"`.
+    pub name: &'static str,
+    pub data_len: usize,
+}
+
+/// Remappable command opcodes. The defaults are the standard SSD1681 ones.
+#[derive(Clone, Copy, Debug)]
+pub struct Commands {
+    pub write_ram: u8,
+    pub write_ram_red: u8,
+    pub read_ram: u8,
+    pub display_update_control: u8,
+    pub master_activation: u8,
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Self {
+            write_ram: WRITE_RAM,
+            write_ram_red: WRITE_RAM_RED,
+            read_ram: READ_RAM,
+            display_update_control: SET_UPDATE_SEQUENCE,
+            master_activation: REFRESH_PANEL,
+        }
+    }
+}
+
+impl<const X: usize, const Y: usize> Ssd1681Config<X, Y> {
+    /// The settings written to controller registers after every reset, for telling whether a new
+    /// config needs them reapplied.
+    pub(crate) fn registers(&self) -> impl PartialEq {
+        (
+            self.use_internal_temp_sensor,
+            self.dummy_line_period,
+            self.gate_line_width,
+            self.gate_scan_start,
+            self.scan_mode,
+            self.reverse_gate_scan,
+            self.border_waveform,
+            self.gate_voltage,
+            self.source_voltage,
+            self.visible_x_offset,
+            self.visible_y_offset,
+        )
+    }
+
+    /// Minimal settle times, suitable for known-good modules.
+    pub fn fast() -> Self {
+        Self {
+            busy_settle_ms: 1,
+            dc_settle_us: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Generous settle times, for clone modules or marginal wiring.
+    pub fn conservative() -> Self {
+        Self {
+            busy_settle_ms: 50,
+            dc_settle_us: 50,
+            ..Self::default()
+        }
+    }
 }
 
 impl<const X: usize, const Y: usize> Default for Ssd1681Config<X, Y> {
     fn default() -> Self {
         Self {
             busy_settle_ms: 20,
+            busy_strategy: BusyStrategy::HardwarePin,
+            busy_timeout_ms: None,
+            wait_for_busy_cycle: false,
+            busy_read_retries: 0,
+            init_max_retries: 0,
+            init_backoff_base_ms: 10,
             dc_settle_us: 10,
+            coarse_delay: false,
+            yield_during_write: false,
+            write_chunk_size: 256,
+            use_internal_temp_sensor: true,
+            wait_for_refresh: true,
+            dummy_line_period: None,
+            gate_line_width: None,
+            gate_scan_start: 0,
+            scan_mode: ScanMode::Sequential,
+            reverse_gate_scan: false,
+            reverse_scanline_bytes: false,
+            border_waveform: None,
+            gate_voltage: None,
+            source_voltage: None,
+            keep_shown_frame_in_red_ram: false,
+            min_refresh_temp_c: None,
+            full_refresh_interval: 0,
+            clear_on_init: true,
+            visible_x_offset: 0,
+            visible_y_offset: 0,
+            power_on_delay_ms: 10,
+            restore_ram_on_wake: true,
+            check_spi_on_init: false,
+            auto_backlight_on_refresh: false,
+            backlight_hold_ms: 0,
+            measure_refresh_duration: false,
+            #[cfg(feature = "trace")]
+            trace: None,
+            commands: Commands::default(),
         }
     }
 }
@@ -0,0 +1,151 @@
+use crate::driver::DisplayDriver;
+use crate::font::{GLYPH_HEIGHT, GLYPH_WIDTH, glyph, is_set};
+use crate::graphics::BufferedDisplay;
+use core::fmt;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+
+const CELL_WIDTH: u32 = GLYPH_WIDTH as u32 + 1;
+const CELL_HEIGHT: u32 = GLYPH_HEIGHT as u32 + 1;
+
+/// Text terminal on top of a [`BufferedDisplay`] using a built-in 5x7 font, for showing a few
+/// lines of text without pulling in embedded-graphics fonts.
+///
+/// Text written with `write!` wraps at the right edge and scrolls the display up once the
+/// last line is full. Writing only renders into the buffer, since [`fmt::Write`] can't await;
+/// call [`Self::flush`] to push completed lines to the panel.
+pub struct TextConsole<'a, D: DisplayDriver, const N: usize> {
+    display: &'a mut BufferedDisplay<D, N>,
+    foreground: BinaryColor,
+    column: u32,
+    row: u32,
+    newline_pending: bool,
+}
+
+impl<'a, D: DisplayDriver, const N: usize> TextConsole<'a, D, N> {
+    pub fn new(display: &'a mut BufferedDisplay<D, N>, foreground: BinaryColor) -> Self {
+        Self {
+            display,
+            foreground,
+            column: 0,
+            row: 0,
+            newline_pending: false,
+        }
+    }
+
+    /// Partially refreshes the panel if a newline has been written since the last flush.
+    pub async fn flush(&mut self) -> Result<(), D::Error> {
+        if self.newline_pending {
+            self.display.flush_dirty().await?;
+            self.newline_pending = false;
+        }
+        Ok(())
+    }
+
+    fn columns(&self) -> u32 {
+        self.display.bounding_box().size.width / CELL_WIDTH
+    }
+
+    fn rows(&self) -> u32 {
+        self.display.bounding_box().size.height / CELL_HEIGHT
+    }
+
+    fn put_char(&mut self, c: char) {
+        match c {
+            '\n' => self.newline(),
+            '\r' => self.column = 0,
+            _ => {
+                if self.column >= self.columns() {
+                    self.newline();
+                }
+                self.draw_glyph(c);
+                self.column += 1;
+            }
+        }
+    }
+
+    fn newline(&mut self) {
+        self.column = 0;
+        self.row += 1;
+        self.newline_pending = true;
+
+        if self.row >= self.rows() {
+            self.scroll();
+            self.row = self.rows().saturating_sub(1);
+        }
+    }
+
+    fn draw_glyph(&mut self, c: char) {
+        let glyph = glyph(c);
+        let origin = Point::new(
+            (self.column * CELL_WIDTH) as i32,
+            (self.row * CELL_HEIGHT) as i32,
+        );
+        let (foreground, background) = (self.foreground, self.foreground.invert());
+
+        let pixels = (0..CELL_HEIGHT).flat_map(move |y| {
+            (0..CELL_WIDTH).map(move |x| {
+                let color = if is_set(glyph, x as usize, y as usize) {
+                    foreground
+                } else {
+                    background
+                };
+                Pixel(origin + Point::new(x as i32, y as i32), color)
+            })
+        });
+        let Ok(()) = self.display.draw_iter(pixels);
+    }
+
+    /// Moves everything up by one text line and blanks the last line.
+    fn scroll(&mut self) {
+        let size = self.display.bounding_box().size;
+        let scrolled = (size.height.saturating_sub(CELL_HEIGHT)) as i32;
+        let background = self.foreground.invert();
+
+        for y in 0..size.height as i32 {
+            for x in 0..size.width as i32 {
+                let color = if y < scrolled {
+                    self.display
+                        .pixel(Point::new(x, y + CELL_HEIGHT as i32))
+                        .unwrap_or(background)
+                } else {
+                    background
+                };
+                let Ok(()) = self.display.draw_iter([Pixel(Point::new(x, y), color)]);
+            }
+        }
+    }
+}
+
+impl<D: DisplayDriver, const N: usize> BufferedDisplay<D, N> {
+    /// Draws `text` in the built-in 5x7 font with `color` ink, its first cell's top-left corner
+    /// at `position`. Glyphs sit in 6x8 cells, every `'\n'` starts a new line below `position`,
+    /// and only the inked pixels are drawn. Anything past the edges is clipped.
+    pub fn draw_text(&mut self, text: &str, position: Point, color: BinaryColor) {
+        let mut origin = position;
+        for c in text.chars() {
+            if c == '\n' {
+                origin = Point::new(position.x, origin.y + CELL_HEIGHT as i32);
+                continue;
+            }
+
+            let glyph = glyph(c);
+            let pixels = (0..GLYPH_HEIGHT).flat_map(move |y| {
+                (0..GLYPH_WIDTH)
+                    .filter(move |&x| is_set(glyph, x, y))
+                    .map(move |x| Pixel(origin + Point::new(x as i32, y as i32), color))
+            });
+            let Ok(()) = self.draw_iter(pixels);
+            origin.x += CELL_WIDTH as i32;
+        }
+    }
+}
+
+impl<D: DisplayDriver, const N: usize> fmt::Write for TextConsole<'_, D, N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.put_char(c);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use crate::driver::DisplayDriver;
+use crate::graphics::BufferedDisplay;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray8, Rgb565};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+/// 4x4 ordered dithering matrix, scaled to thresholds by [`threshold`].
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Draw target that accepts [`Gray8`] pixels and ordered-dithers them into a
+/// [`BufferedDisplay`], for showing photographs on the 1-bit panel.
+///
+/// Created with [`BufferedDisplay::dithered`]. Dithering is applied in logical (rotated)
+/// coordinates, so the pattern stays regular whatever the rotation.
+pub struct DitheredDisplay<'a, D: DisplayDriver, const N: usize> {
+    display: &'a mut BufferedDisplay<D, N>,
+}
+
+impl<D: DisplayDriver, const N: usize> BufferedDisplay<D, N> {
+    pub fn dithered(&mut self) -> DitheredDisplay<'_, D, N> {
+        DitheredDisplay { display: self }
+    }
+
+    /// Fills `area` with `colors`, row by row from its top-left corner, converting each to its
+    /// luminance and ordered-dithering it like [`DitheredDisplay`]. Saves converting an RGB565
+    /// image to [`Gray8`] first.
+    pub fn fill_rgb_dithered(&mut self, area: Rectangle, colors: impl IntoIterator<Item = Rgb565>) {
+        let pixels = area
+            .points()
+            .zip(colors)
+            .map(|(point, color)| Pixel(point, dither(point, Gray8::from(color).luma())));
+        let Ok(()) = self.draw_iter(pixels);
+    }
+}
+
+impl<D: DisplayDriver, const N: usize> Dimensions for DitheredDisplay<'_, D, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.display.bounding_box()
+    }
+}
+
+impl<D: DisplayDriver, const N: usize> DrawTarget for DitheredDisplay<'_, D, N> {
+    type Color = Gray8;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.display.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(point, dither(point, color.luma()))),
+        )
+    }
+}
+
+/// Maps a luma value to black or white by comparing it against the Bayer threshold for `point`.
+pub(crate) fn dither(point: Point, luma: u8) -> BinaryColor {
+    BinaryColor::from(luma > threshold(point))
+}
+
+fn threshold(point: Point) -> u8 {
+    let x = point.x.rem_euclid(4) as usize;
+    let y = point.y.rem_euclid(4) as usize;
+    BAYER_4X4[y][x] * 16 + 8
+}
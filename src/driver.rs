@@ -1,124 +1,1973 @@
 use crate::commands::{
-    REFRESH_PANEL, RESET, SET_DATA_ENTRY_MODE, SET_RAM_X, SET_RAM_Y, SET_TEMPERATURE_SENSOR,
-    SET_UPDATE_SEQUENCE, SET_X_POINTER, SET_Y_POINTER, WRITE_RAM,
+    DEEP_SLEEP_MODE, DRIVER_OUTPUT_CONTROL, NOP, READ_DISPLAY_OPTION, READ_STATUS,
+    READ_TEMPERATURE, RESET, SET_BORDER_WAVEFORM, SET_DATA_ENTRY_MODE, SET_DUMMY_LINE_PERIOD,
+    SET_GATE_LINE_WIDTH, SET_GATE_SCAN_START, SET_GATE_VOLTAGE, SET_RAM_READ_OPTION, SET_RAM_X,
+    SET_RAM_Y, SET_SOURCE_VOLTAGE, SET_TEMPERATURE_SENSOR, SET_VCOM_SENSE_DURATION, SET_X_POINTER,
+    SET_Y_POINTER, VCOM_SENSE, WRITE_LUT, WRITE_VCOM,
 };
-use crate::config::Ssd1681Config;
+use crate::config::{BusyStrategy, Commands, ScanMode, Ssd1681Config};
 use crate::errors::DisplayError;
+use crate::graphics::Rotation;
+use crate::paged::Page;
+use core::future::{Future, poll_fn};
+use core::pin::{Pin, pin};
+use core::task::{Context, Poll};
+use embedded_graphics::prelude::{Point, Size};
+use embedded_graphics::primitives::Rectangle;
 use embedded_hal::digital::{InputPin, OutputPin};
 use embedded_hal_async::delay::DelayNs;
 use embedded_hal_async::digital::Wait;
-use embedded_hal_async::spi::SpiDevice;
+use embedded_hal_async::spi::{Operation, SpiDevice};
+
+/// Size in bytes of a packed frame for an `x` by `y` panel, one bit per pixel.
+pub const fn buffer_len(x: usize, y: usize) -> usize {
+    frame_len(ColorMode::BlackWhite, x, y)
+}
+
+/// Size in bytes of a packed frame for an `x` by `y` panel in `mode`: one bit per pixel for each
+/// RAM plane the mode uses, planes stored back to back.
+pub const fn frame_len(mode: ColorMode, x: usize, y: usize) -> usize {
+    mode.planes() * x * y / 8
+}
+
+/// Color mode of a frame buffer, deciding how many RAM planes it spans.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorMode {
+    BlackWhite,
+    /// Black/white plane followed by the red plane.
+    TriColor,
+    /// Two bits per pixel for a 4-level gray LUT: the plane of high bits, written to the
+    /// black/white bank, followed by the plane of low bits for the red bank.
+    Grayscale,
+}
+
+impl ColorMode {
+    pub const fn planes(self) -> usize {
+        match self {
+            ColorMode::BlackWhite => 1,
+            ColorMode::TriColor | ColorMode::Grayscale => 2,
+        }
+    }
+}
 
 pub trait DisplayDriver {
     type Error;
 
-    const X: usize;
-    const Y: usize;
+    const X: usize;
+    const Y: usize;
+    /// Size in bytes of a packed frame for this panel.
+    const BUFFER_LEN: usize = buffer_len(Self::X, Self::Y);
+
+    /// Writes a full frame and refreshes the panel. `buffer` must be exactly
+    /// [`Self::BUFFER_LEN`] bytes; frames of any other length are rejected before anything is
+    /// sent, rather than partly overwriting controller RAM.
+    async fn draw_frame(&mut self, buffer: &[u8]) -> Result<(), Self::Error>;
+
+    /// Writes the part of `buffer` covered by `area` (in hardware coordinates) and refreshes only
+    /// that region of the panel.
+    async fn draw_partial(&mut self, buffer: &[u8], area: Rectangle) -> Result<(), Self::Error>;
+
+    /// Like [`Self::draw_partial`], but writes only the black/white RAM bank, leaving the red
+    /// plane of a tri-color panel as it is. Drivers without separate banks draw a regular
+    /// partial update.
+    async fn draw_partial_black_white(
+        &mut self,
+        buffer: &[u8],
+        area: Rectangle,
+    ) -> Result<(), Self::Error> {
+        self.draw_partial(buffer, area).await
+    }
+
+    /// Writes both RAM banks of a tri-color panel and runs a full refresh.
+    async fn draw_tri_color_frame(
+        &mut self,
+        black_white: &[u8],
+        red: &[u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Rewrites only the red RAM bank of a tri-color panel and runs a full refresh, leaving the
+    /// black/white bank as last written. `black_white` is what that bank should hold; drivers
+    /// that can't write the banks separately redraw both.
+    async fn draw_red_frame(&mut self, black_white: &[u8], red: &[u8]) -> Result<(), Self::Error> {
+        self.draw_tri_color_frame(black_white, red).await
+    }
+
+    /// Like [`Self::draw_frame`], but reads RAM back before refreshing and writes the frame once
+    /// more if it doesn't match. Drivers that can't read back just draw the frame.
+    async fn draw_frame_verified(&mut self, buffer: &[u8]) -> Result<(), Self::Error> {
+        self.draw_frame(buffer).await
+    }
+
+    /// Writes a frame and starts a full refresh without waiting for it to finish; poll
+    /// [`Self::poll_frame`] for completion. Drivers that can't split the two just draw the
+    /// frame.
+    async fn start_frame(&mut self, buffer: &[u8]) -> Result<(), Self::Error> {
+        self.draw_frame(buffer).await
+    }
+
+    /// Returns whether the refresh started by [`Self::start_frame`] has finished.
+    fn poll_frame(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    /// Waits for the refresh started by [`Self::start_frame`] to finish, letting the executor
+    /// sleep meanwhile where the driver can await the completion edge.
+    async fn wait_for_frame(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn enable_backlight(&mut self) -> Result<(), Self::Error>;
+    async fn disable_backlight(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Controller found by [`Ssd1681::detect_controller`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Controller {
+    Ssd1681,
+    /// An SSD1680, with RAM for 176 columns of up to 296 gate lines.
+    Ssd1680,
+    /// Nothing read back as written, e.g. on a bus that can't read from the panel.
+    Unknown,
+}
+
+/// RAM bank written by [`Ssd1681::write_ram`] or read after [`Ssd1681::set_ram_read_source`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RamPlane {
+    /// Black/white RAM (`0x24`).
+    BlackWhite,
+    /// Red RAM (`0x26`), or the previous frame on black/white panels.
+    Red,
+}
+
+/// What the red RAM bank is put back to from a retained frame.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RetainedRed {
+    /// A copy of the black/white frame, as black/white frames leave it.
+    Mirror,
+    /// The red plane of a tri-color frame, kept after the black/white one.
+    Retained,
+    /// Red content there was no room to keep; cleared instead.
+    Lost,
+}
+
+/// Refresh waveform, as used by [`Ssd1681::play`] and suggested by
+/// [`BufferedDisplay::recommend_update_mode`](crate::graphics::BufferedDisplay::recommend_update_mode).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpdateMode {
+    /// Display mode 1, the full waveform: slow, but clears ghosting.
+    Full,
+    /// Display mode 2, the partial waveform: fast, driven by the difference to the previous
+    /// frame.
+    Partial,
+    /// Display mode 2 with [`CURSOR_BLINK_LUT`]: a single short pulse on the pixels that change
+    /// and nothing on the rest, so a blinking cursor never flashes. Ghosting builds up quickly;
+    /// a full refresh has to follow every so often, e.g. through `full_refresh_interval`.
+    CursorBlink,
+}
+
+/// Waveform for [`UpdateMode::CursorBlink`]: one 10-frame phase driving pixels that turn
+/// white to VSL and pixels that turn black to VSH1, leaving unchanged pixels and VCOM at rest.
+pub const CURSOR_BLINK_LUT: [u8; 153] = {
+    let mut lut = [0; 153];
+    // voltage selection, one row of 12 groups per transition; phase A of group 0 only
+    lut[12] = 0x80;
+    lut[24] = 0x40;
+    // timing of group 0
+    lut[60] = 0x0a;
+    // frame rate
+    let mut i = 144;
+    while i < 150 {
+        lut[i] = 0x22;
+        i += 1;
+    }
+    lut
+};
+
+/// Where refreshes take their waveform from; see [`Ssd1681::use_register_lut`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LutSource {
+    /// The OTP LUT for the measured temperature, loaded by each refresh.
+    Otp,
+    /// The LUT register, as written by [`Ssd1681::load_custom_lut`].
+    Register,
+}
+
+/// Phases run by a master activation, as selected by display update control 2 (`0x22`); pass to
+/// [`Ssd1681::set_display_update_control`].
+///
+/// The phases run in field order. Start from one of the constants, e.g.
+/// `UpdateSequence::DISPLAY_ONLY.with_mode(UpdateMode::Partial)`, or set the fields directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UpdateSequence {
+    pub enable_clock: bool,
+    pub enable_analog: bool,
+    pub load_temperature: bool,
+    /// Load the OTP LUT for `mode` and the loaded temperature.
+    pub load_lut: bool,
+    /// Display mode of the LUT load and display phases; [`UpdateMode::CursorBlink`] selects
+    /// display mode 2 like [`UpdateMode::Partial`].
+    pub mode: UpdateMode,
+    pub display: bool,
+    pub disable_analog: bool,
+    pub disable_clock: bool,
+}
+
+impl UpdateSequence {
+    const NONE: Self = Self {
+        enable_clock: false,
+        enable_analog: false,
+        load_temperature: false,
+        load_lut: false,
+        mode: UpdateMode::Full,
+        display: false,
+        disable_analog: false,
+        disable_clock: false,
+    };
+
+    /// Full refresh with the OTP LUT (`0xF7`).
+    pub const FULL: Self = Self {
+        enable_clock: true,
+        enable_analog: true,
+        load_temperature: true,
+        load_lut: true,
+        display: true,
+        disable_analog: true,
+        disable_clock: true,
+        ..Self::NONE
+    };
+    /// Partial refresh with the OTP LUT (`0xFF`).
+    pub const PARTIAL: Self = Self::FULL.with_mode(UpdateMode::Partial);
+    /// Display phase only, with the LUT already loaded (`0xC7`).
+    pub const DISPLAY_ONLY: Self = Self {
+        load_temperature: false,
+        load_lut: false,
+        ..Self::FULL
+    };
+    /// Loads the temperature and LUT without displaying (`0xB1`).
+    pub const PRELOAD_LUT: Self = Self {
+        enable_clock: true,
+        load_temperature: true,
+        load_lut: true,
+        disable_clock: true,
+        ..Self::NONE
+    };
+    /// Loads the temperature only (`0xA1`).
+    pub const LOAD_TEMPERATURE: Self = Self {
+        load_lut: false,
+        ..Self::PRELOAD_LUT
+    };
+    /// Switches the clock and analog supplies on and leaves them on (`0xC0`).
+    pub const POWER_ON: Self = Self {
+        enable_clock: true,
+        enable_analog: true,
+        ..Self::NONE
+    };
+    /// Switches the analog supplies and clock off (`0x03`).
+    pub const POWER_OFF: Self = Self {
+        disable_analog: true,
+        disable_clock: true,
+        ..Self::NONE
+    };
+
+    pub const fn with_mode(self, mode: UpdateMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    /// The control byte sent with `0x22`.
+    pub const fn bits(self) -> u8 {
+        let flags = [
+            self.enable_clock,
+            self.enable_analog,
+            self.load_temperature,
+            self.load_lut,
+            false,
+            self.display,
+            self.disable_analog,
+            self.disable_clock,
+        ];
+
+        let mut bits = self.mode.sequence_bits();
+        let mut i = 0;
+        while i < flags.len() {
+            if flags[i] {
+                bits |= 0x80 >> i;
+            }
+            i += 1;
+        }
+        bits
+    }
+}
+
+impl UpdateMode {
+    /// Display mode bit of the display update control sequence.
+    const fn sequence_bits(self) -> u8 {
+        match self {
+            UpdateMode::Full => 0x00,
+            UpdateMode::Partial | UpdateMode::CursorBlink => 0x08,
+        }
+    }
+}
+
+impl RamPlane {
+    fn write_command(self, commands: &Commands) -> u8 {
+        match self {
+            RamPlane::BlackWhite => commands.write_ram,
+            RamPlane::Red => commands.write_ram_red,
+        }
+    }
+}
+
+/// Placeholder for the power pin of a driver created with [`Ssd1681::new`], whose panel supply
+/// isn't switched.
+pub struct NoPowerPin;
+
+impl embedded_hal::digital::ErrorType for NoPowerPin {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for NoPowerPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// BUSY input of the driver.
+///
+/// Implemented for every [`InputPin`] that also implements the async [`Wait`]. Pins without
+/// `Wait` can be wrapped in [`PolledBusy`], which suits [`BusyStrategy::Poll`] and
+/// [`BusyStrategy::FixedDelay`].
+pub trait BusyPin: InputPin {
+    /// Waits until BUSY reads `high`.
+    async fn wait_for_level(&mut self, high: bool) -> Result<(), Self::Error>;
+}
+
+impl<T: InputPin + Wait> BusyPin for T {
+    async fn wait_for_level(&mut self, high: bool) -> Result<(), Self::Error> {
+        if high {
+            self.wait_for_high().await
+        } else {
+            self.wait_for_low().await
+        }
+    }
+}
+
+/// A BUSY pin that can only be read, not awaited, for HALs whose input pins don't implement
+/// [`Wait`]. Meant for [`BusyStrategy::Poll`]; with [`BusyStrategy::HardwarePin`] it reads the
+/// pin continuously, yielding to the executor between reads.
+pub struct PolledBusy<P>(pub P);
+
+impl<P: InputPin> embedded_hal::digital::ErrorType for PolledBusy<P> {
+    type Error = P::Error;
+}
+
+impl<P: InputPin> InputPin for PolledBusy<P> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.0.is_high()
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.0.is_low()
+    }
+}
+
+impl<P: InputPin> BusyPin for PolledBusy<P> {
+    async fn wait_for_level(&mut self, high: bool) -> Result<(), Self::Error> {
+        while self.0.is_high()? != high {
+            YieldNow(false).await;
+        }
+        Ok(())
+    }
+}
+
+pub struct Ssd1681<const X: usize, const Y: usize, SPI, BUSY, DC, BL, RST, DELAY, PWR = NoPowerPin>
+where
+    SPI: SpiDevice,
+    BUSY: BusyPin,
+    DC: OutputPin,
+    BL: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+    PWR: OutputPin,
+{
+    spi: SPI,
+    busy_pin: BUSY,
+    dc_pin: DC,
+    backlight_pin: Option<BL>,
+    reset_pin: RST,
+    power_pin: Option<PWR>,
+    delay: DELAY,
+    config: Ssd1681Config<X, Y>,
+    partial_refreshes: u16,
+    retained_frame: Option<&'static mut [u8]>,
+    retained_red: RetainedRed,
+    last_refresh_ms: Option<u32>,
+    red_ram_stale: bool,
+    register_lut: bool,
+    ram_transfer_hook: Option<fn(&mut SPI, bool)>,
+}
+
+impl<
+    const X: usize,
+    const Y: usize,
+    SPI: SpiDevice,
+    BUSY: BusyPin,
+    DC: OutputPin,
+    BL: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+> Ssd1681<X, Y, SPI, BUSY, DC, BL, RST, DELAY>
+{
+    /// Creates the driver and initialises the panel.
+    ///
+    /// Every pin must be a different GPIO. Ownership rules this out for HALs that hand out each
+    /// pin once, but some can create several handles to the same pin, which the driver has no
+    /// way to tell apart: a backlight handle aliasing the reset line, for example, holds the
+    /// controller in reset whenever the backlight is switched off. Pass `None` for a backlight
+    /// that isn't wired rather than reusing another pin.
+    pub async fn new(
+        spi: SPI,
+        busy_pin: BUSY,
+        dc_pin: DC,
+        reset_pin: RST,
+        backlight_pin: Option<BL>,
+        delay: DELAY,
+        config: Ssd1681Config<X, Y>,
+    ) -> Result<Self, DisplayError> {
+        Self::with_power_pin(
+            spi,
+            busy_pin,
+            dc_pin,
+            reset_pin,
+            backlight_pin,
+            None,
+            delay,
+            config,
+        )
+        .await
+    }
+}
+
+impl<
+    const X: usize,
+    const Y: usize,
+    SPI: SpiDevice,
+    BUSY: BusyPin,
+    DC: OutputPin,
+    BL: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+    PWR: OutputPin,
+> Ssd1681<X, Y, SPI, BUSY, DC, BL, RST, DELAY, PWR>
+{
+    /// Like [`Self::new`], for boards that switch the panel supply through `power_pin` (driven
+    /// high for on). The supply is switched on before init and on [`Self::wake`], and cut by
+    /// [`Self::power_off`].
+    #[allow(clippy::too_many_arguments)]
+    pub async fn with_power_pin(
+        spi: SPI,
+        busy_pin: BUSY,
+        dc_pin: DC,
+        reset_pin: RST,
+        backlight_pin: Option<BL>,
+        power_pin: Option<PWR>,
+        delay: DELAY,
+        config: Ssd1681Config<X, Y>,
+    ) -> Result<Self, DisplayError> {
+        let mut new = Self {
+            spi,
+            busy_pin,
+            dc_pin,
+            backlight_pin,
+            reset_pin,
+            power_pin,
+            delay,
+            config,
+            partial_refreshes: 0,
+            retained_frame: None,
+            retained_red: RetainedRed::Mirror,
+            last_refresh_ms: None,
+            red_ram_stale: false,
+            register_lut: false,
+            ram_transfer_hook: None,
+        };
+
+        new.init().await?;
+
+        Ok(new)
+    }
+
+    /// Swaps in a new configuration at runtime. Timing settings take effect immediately; if any
+    /// setting backed by a controller register changed, every such register is written again,
+    /// settings left at their defaults with the reset values. The OTP dummy line period and gate
+    /// line width can't be written back that way: setting those to `None` takes effect with the
+    /// next reset.
+    pub async fn apply_config(&mut self, config: Ssd1681Config<X, Y>) -> Result<(), DisplayError> {
+        let old = core::mem::replace(&mut self.config, config);
+
+        if old.registers() != self.config.registers() {
+            self.reset_config_registers().await?;
+            if let Some(period) = self.config.dummy_line_period {
+                self.set_dummy_line_period(period).await?;
+            }
+            if let Some(width) = self.config.gate_line_width {
+                self.set_gate_line_width(width).await?;
+            }
+            self.set_gate_scan_start(self.config.gate_scan_start)
+                .await?;
+            self.set_driver_output_control().await?;
+        }
+        Ok(())
+    }
+
+    /// Recovers a wedged controller: hardware and software reset followed by the same init
+    /// sequence as [`Self::new`].
+    pub async fn reset_and_reinit(&mut self) -> Result<(), DisplayError> {
+        self.partial_refreshes = 0;
+        self.init().await
+    }
+
+    /// Cancels a running refresh, e.g. one started by [`DisplayDriver::start_frame`], with a
+    /// hardware and software reset, leaving the driver ready for the next frame without the
+    /// clearing refresh of [`Self::reset_and_reinit`].
+    ///
+    /// The panel keeps the partially updated image until the next refresh, which should be a
+    /// full one; a partial update would only drive the pixels it thinks changed. With a buffer
+    /// given to [`Self::retain_frames_in`], the last drawn frame is written back to both RAM
+    /// banks, as on [`Self::wake`]. With `auto_backlight_on_refresh` set the backlight is
+    /// switched off, in case the refresh was cancelled by dropping its future.
+    pub async fn abort_refresh(&mut self) -> Result<(), DisplayError> {
+        if self.config.auto_backlight_on_refresh {
+            self.disable_backlight().await?;
+        }
+        self.reset().await?;
+
+        if let Some(frame) = self.retained_frame.take() {
+            let restored = self.restore_frame(frame).await;
+            self.retained_frame = Some(frame);
+            restored?;
+            self.red_ram_stale = false;
+        }
+        Ok(())
+    }
+
+    /// Writes the border waveform, driving voltages, data entry mode and temperature sensor
+    /// selection again, to the configured values or, for those left `None`, the reset values.
+    /// Reverts experiments with [`Self::set_border_waveform`] and the like.
+    ///
+    /// Unlike [`Self::reset_and_reinit`] this neither touches the reset pin nor refreshes, so the
+    /// panel doesn't glitch; RAM and the remaining registers are left as they are.
+    pub async fn reset_config_registers(&mut self) -> Result<(), DisplayError> {
+        self.set_data_entry_mode().await?;
+        self.set_ram_x(0, (X - 1) / 8).await?;
+        self.set_ram_y(0, Y - 1).await?;
+        if self.config.use_internal_temp_sensor {
+            self.set_internal_temp_sensor().await?;
+        } else {
+            // external sensor, the reset value
+            self.send_command(SET_TEMPERATURE_SENSOR, Some(&[0x48]))
+                .await?;
+        }
+        self.set_border_waveform(self.config.border_waveform.unwrap_or(0xc0))
+            .await?;
+        self.set_gate_voltage(self.config.gate_voltage.unwrap_or(0x00))
+            .await?;
+        self.set_source_voltage(self.config.source_voltage.unwrap_or([0x41, 0xa8, 0x32]))
+            .await
+    }
+
+    /// Calls `hook` with `true` right before the data of every RAM write and with `false` right
+    /// after it, e.g. to raise the clock of a shared-bus device for the frame transfer only:
+    /// `SpiDevice` has no way to change speed per transaction, but wrappers such as
+    /// embassy-embedded-hal's `SpiDeviceWithConfig` can be reconfigured between them. Commands,
+    /// register writes and reads all run outside the two calls.
+    pub fn set_ram_transfer_hook(&mut self, hook: fn(&mut SPI, bool)) {
+        self.ram_transfer_hook = Some(hook);
+    }
+
+    /// Keeps a copy of every frame drawn in `buffer` (`X * Y / 8` bytes), so [`Self::wake`] can
+    /// put it back into controller RAM. Give it twice that to keep the red plane of tri-color
+    /// frames too; otherwise red content is cleared when a tri-color frame is restored.
+    pub fn retain_frames_in(&mut self, buffer: &'static mut [u8]) {
+        self.retained_frame = Some(buffer);
+    }
+
+    /// Puts the controller into deep sleep. The panel keeps showing its image; call
+    /// [`Self::wake`] before drawing again.
+    pub async fn sleep(&mut self) -> Result<(), DisplayError> {
+        // BUSY stays high for as long as the controller sleeps, so don't wait on it
+        self.start_command(DEEP_SLEEP_MODE, 1).await?;
+        self.send_data(&[0x01]).await
+    }
+
+    /// Wakes the controller from deep sleep or [`Self::power_off`] with a hardware reset and
+    /// reapplies the configuration, without the clearing refresh done by [`Self::new`].
+    ///
+    /// The reset leaves controller RAM out of step with what the panel still shows, which
+    /// corrupts the next partial update. With `restore_ram_on_wake` set and a buffer given to
+    /// [`Self::retain_frames_in`], the last drawn frame is written back to both RAM banks.
+    pub async fn wake(&mut self) -> Result<(), DisplayError> {
+        self.power_on().await?;
+        self.reset().await?;
+
+        if self.config.restore_ram_on_wake
+            && let Some(frame) = self.retained_frame.take()
+        {
+            let restored = self.restore_frame(frame).await;
+            self.retained_frame = Some(frame);
+            restored?;
+        }
+        Ok(())
+    }
+
+    /// Cuts the panel supply through the power pin, if there is one. The panel keeps showing its
+    /// image but controller RAM is lost; call [`Self::wake`] before drawing again.
+    pub async fn power_off(&mut self) -> Result<(), DisplayError> {
+        self.wait_for_idle().await?;
+        if let Some(pin) = self.power_pin.as_mut() {
+            pin.set_low().map_err(|_| DisplayError::PowerPinFailure)?;
+        }
+        Ok(())
+    }
+
+    async fn power_on(&mut self) -> Result<(), DisplayError> {
+        if let Some(pin) = self.power_pin.as_mut() {
+            pin.set_high().map_err(|_| DisplayError::PowerPinFailure)?;
+            self.delay.delay_ms(self.config.power_on_delay_ms).await;
+        }
+        Ok(())
+    }
+
+    /// Writes the retained frame, which is what the panel shows, to the red RAM bank if a
+    /// non-blocking refresh left it behind.
+    async fn sync_red_ram(&mut self) -> Result<(), DisplayError> {
+        if !core::mem::take(&mut self.red_ram_stale) {
+            return Ok(());
+        }
+
+        if let Some(frame) = self.retained_frame.take() {
+            let synced = self
+                .write_ram(RamPlane::Red, black_white_plane::<X, Y>(frame))
+                .await;
+            self.retained_frame = Some(frame);
+            synced?;
+        }
+        Ok(())
+    }
+
+    async fn restore_frame(&mut self, frame: &[u8]) -> Result<(), DisplayError> {
+        let black_white = black_white_plane::<X, Y>(frame);
+        self.write_ram(RamPlane::BlackWhite, black_white).await?;
+        match self.retained_red {
+            RetainedRed::Mirror => self.write_ram(RamPlane::Red, black_white).await,
+            RetainedRed::Retained => {
+                let len = black_white.len();
+                self.write_ram(RamPlane::Red, &frame[len..2 * len]).await
+            }
+            RetainedRed::Lost => self.write_ram_with(RamPlane::Red, &|_| 0x00).await,
+        }
+    }
+
+    fn retain_frame(&mut self, buffer: &[u8]) {
+        self.retained_red = RetainedRed::Mirror;
+        if let Some(retained) = self.retained_frame.as_deref_mut() {
+            let len = retained.len().min(buffer.len());
+            retained[..len].copy_from_slice(&buffer[..len]);
+        }
+    }
+
+    /// Keeps the red plane of a tri-color frame after the black/white one, if there's room.
+    fn retain_red(&mut self, red: &[u8]) {
+        let len = buffer_len(X, Y);
+        let room = self
+            .retained_frame
+            .as_deref_mut()
+            .and_then(|retained| retained.get_mut(len..2 * len));
+        self.retained_red = match room {
+            Some(room) => {
+                room.copy_from_slice(red);
+                RetainedRed::Retained
+            }
+            None => RetainedRed::Lost,
+        };
+    }
+
+    /// Writes a test pattern to RAM and reads it back, failing with
+    /// [`DisplayError::SpiConfigSuspect`] if it doesn't survive the round trip.
+    ///
+    /// The SSD1681 expects SPI mode 0, MSB first; any other mode shifts or mangles the bits, which
+    /// otherwise only shows up as a garbled image. Overwrites the first bytes of the black/white
+    /// RAM, and requires an SPI bus that can read from the panel.
+    pub async fn check_spi(&mut self) -> Result<(), DisplayError> {
+        const PATTERN: [u8; 4] = [0xa5, 0x3c, 0x01, 0x80];
+
+        self.set_pointer(0, 0).await?;
+        self.send_command(self.config.commands.write_ram, Some(&PATTERN))
+            .await?;
+
+        if self.read_back_matches(PATTERN.into_iter()).await? {
+            Ok(())
+        } else {
+            Err(DisplayError::SpiConfigSuspect)
+        }
+    }
+
+    /// Selects the RAM bank that `READ_RAM` (`0x27`) reads from (read RAM option, `0x41`). It has
+    /// to be set before issuing the read.
+    pub async fn set_ram_read_source(&mut self, plane: RamPlane) -> Result<(), DisplayError> {
+        let source = match plane {
+            RamPlane::BlackWhite => 0x00,
+            RamPlane::Red => 0x01,
+        };
+        self.send_command(SET_RAM_READ_OPTION, Some(&[source]))
+            .await
+    }
+
+    /// Reads the black/white RAM back and compares it with `expected`, to detect frames corrupted
+    /// in transit (e.g. over a long flex cable). Requires an SPI bus that can read from the panel.
+    pub async fn verify_frame(&mut self, expected: &[u8]) -> Result<bool, DisplayError> {
+        if self.config.reverse_scanline_bytes {
+            let rows = expected.chunks(X / 8);
+            self.read_back_matches(rows.flat_map(|row| row.iter().rev().copied()))
+                .await
+        } else {
+            self.read_back_matches(expected.iter().copied()).await
+        }
+    }
+
+    /// Whether the black/white RAM, read from the start, holds the bytes of `expected`.
+    async fn read_back_matches(
+        &mut self,
+        mut expected: impl Iterator<Item = u8>,
+    ) -> Result<bool, DisplayError> {
+        self.set_ram_read_source(RamPlane::BlackWhite).await?;
+        self.set_pointer(0, 0).await?;
+        self.start_command(self.config.commands.read_ram, expected.size_hint().0)
+            .await?;
+        self.start_data().await?;
+
+        // the first byte clocked out after READ_RAM is a dummy
+        self.read_spi(&mut [0]).await?;
+
+        let mut matches = true;
+        let (mut read, mut wanted) = ([0u8; 32], [0u8; 32]);
+        loop {
+            let mut len = 0;
+            for (slot, byte) in wanted.iter_mut().zip(&mut expected) {
+                *slot = byte;
+                len += 1;
+            }
+            self.read_spi(&mut read[..len]).await?;
+            matches &= read[..len] == wanted[..len];
+            if len < wanted.len() {
+                break;
+            }
+        }
+
+        self.wait_while_busy().await?;
+        self.end_ram_access(self.config.commands.read_ram).await?;
+        Ok(matches)
+    }
+
+    /// Measures the panel's optimal VCOM and returns it in millivolts (always negative).
+    ///
+    /// Sensing powers up the analog supplies and holds for one second before sampling. The VCOM
+    /// register counts in -25mV steps, so `0x08` is -0.2V and `0x78` is -3.0V; pass the result to
+    /// [`Self::set_vcom`] to use it.
+    pub async fn sense_vcom(&mut self) -> Result<i16, DisplayError> {
+        self.set_display_update_control(UpdateSequence::POWER_ON)
+            .await?;
+        self.master_activation().await?;
+        self.send_command(SET_VCOM_SENSE_DURATION, Some(&[0x00]))
+            .await?;
+        self.send_command(VCOM_SENSE, None).await?;
+
+        // the display option readout starts with the OTP VCOM selection, then the VCOM register
+        let mut option = [0u8; 2];
+        self.read_command(READ_DISPLAY_OPTION, &mut option).await?;
+
+        self.set_display_update_control(UpdateSequence::POWER_OFF)
+            .await?;
+        self.master_activation().await?;
+
+        Ok(-(option[1] as i16) * 25)
+    }
+
+    /// Reads the chip ID bits of the status register (`0x2F`, `A[1:0]`).
+    ///
+    /// The SSD1681 has no dedicated identification register, so this is only two bits wide and
+    /// mainly tells a responding controller apart from a floating bus. Requires an SPI bus that
+    /// can read from the panel.
+    pub async fn read_id(&mut self) -> Result<u16, DisplayError> {
+        let mut status = [0u8; 1];
+        self.read_command(READ_STATUS, &mut status).await?;
+        Ok((status[0] & 0x03) as u16)
+    }
+
+    /// Tells an SSD1681 from an SSD1680, which takes the same commands but has RAM for 176 source
+    /// outputs (22 byte columns) where the SSD1681 has 200 (25). [`Self::read_id`] reads the same
+    /// on both.
+    ///
+    /// A probe byte is written to byte column 24, which only the SSD1681 has, another to column 0,
+    /// and both are read back. This overwrites two bytes of black/white RAM in the first gate
+    /// line, so redraw the frame afterwards. Requires an SPI bus that can read from the panel.
+    pub async fn detect_controller(&mut self) -> Result<Controller, DisplayError> {
+        const LAST_SSD1681_COLUMN: u8 = 24;
+        const PROBES: [(u8, u8); 2] = [(LAST_SSD1681_COLUMN, 0x5a), (0, 0xa5)];
+
+        // raw addresses, as the probe column lies outside the visible area of most panels
+        self.send_command(SET_RAM_X, Some(&[0, LAST_SSD1681_COLUMN]))
+            .await?;
+        self.send_command(SET_RAM_Y, Some(&[0, 0, 0, 0])).await?;
+        for (column, probe) in PROBES {
+            self.send_command(SET_X_POINTER, Some(&[column])).await?;
+            self.send_command(SET_Y_POINTER, Some(&[0, 0])).await?;
+            self.send_command(self.config.commands.write_ram, Some(&[probe]))
+                .await?;
+        }
+
+        let mut read = [0u8; 2];
+        self.set_ram_read_source(RamPlane::BlackWhite).await?;
+        for ((column, _), byte) in PROBES.into_iter().zip(&mut read) {
+            self.send_command(SET_X_POINTER, Some(&[column])).await?;
+            self.send_command(SET_Y_POINTER, Some(&[0, 0])).await?;
+            // the first byte clocked out after READ_RAM is a dummy
+            let mut dummy_and_byte = [0u8; 2];
+            self.read_command(self.config.commands.read_ram, &mut dummy_and_byte)
+                .await?;
+            self.end_ram_access(self.config.commands.read_ram).await?;
+            *byte = dummy_and_byte[1];
+        }
+
+        self.set_ram_x(0, (X - 1) / 8).await?;
+        self.set_ram_y(0, Y - 1).await?;
+
+        let [(_, wide_probe), (_, narrow_probe)] = PROBES;
+        Ok(match read {
+            [wide, narrow] if wide == wide_probe && narrow == narrow_probe => Controller::Ssd1681,
+            [_, narrow] if narrow == narrow_probe => Controller::Ssd1680,
+            _ => Controller::Unknown,
+        })
+    }
+
+    /// Measures the panel temperature with the temperature sensor and returns it in whole °C,
+    /// rounded down. Requires an SPI bus that can read from the panel.
+    pub async fn read_temperature(&mut self) -> Result<i8, DisplayError> {
+        // clock on, load temperature, clock off
+        self.set_display_update_control(UpdateSequence::LOAD_TEMPERATURE)
+            .await?;
+        self.master_activation().await?;
+
+        // 12-bit two's complement in 1/16 °C, most significant bits first
+        let mut raw = [0u8; 2];
+        self.read_command(READ_TEMPERATURE, &mut raw).await?;
+        let sixteenths = i16::from_be_bytes(raw) >> 4;
+        Ok((sixteenths >> 4) as i8)
+    }
+
+    /// Writes a waveform LUT (`0x32`) to use instead of the one in OTP, and switches to it with
+    /// [`Self::use_register_lut`].
+    ///
+    /// Resets, including [`Self::wake`], fall back to the OTP LUT; load it again afterwards.
+    pub async fn load_custom_lut(&mut self, lut: &[u8; 153]) -> Result<(), DisplayError> {
+        self.send_command(WRITE_LUT, Some(lut)).await?;
+        self.use_register_lut(true);
+        Ok(())
+    }
+
+    /// Selects the waveform used by the following refreshes: with `enable`, the LUT register as
+    /// written by [`Self::load_custom_lut`]; otherwise the OTP LUT for the measured temperature.
+    ///
+    /// The choice lives in the display update control (`0x22`) sequences: loading the OTP LUT
+    /// (bit 4, with the temperature read it needs) would overwrite the register, so those bits
+    /// are dropped while it is in use, e.g. `0xC7` instead of `0xF7` for a full refresh.
+    pub fn use_register_lut(&mut self, enable: bool) {
+        self.register_lut = enable;
+    }
+
+    /// The waveform source the next refresh will use. The controller has no status bit for it,
+    /// so this reflects the last [`Self::use_register_lut`] or [`Self::load_custom_lut`] call
+    /// and the resets since.
+    pub fn configured_lut_source(&self) -> LutSource {
+        if self.register_lut {
+            LutSource::Register
+        } else {
+            LutSource::Otp
+        }
+    }
+
+    /// Reads the temperature sensor and loads the LUT from `table` whose temperature (°C) is
+    /// closest to it, returning the measured temperature. Does nothing beyond the reading if
+    /// `table` is empty.
+    pub async fn set_lut_from_temperature(
+        &mut self,
+        table: &[(i8, [u8; 153])],
+    ) -> Result<i8, DisplayError> {
+        let temp = self.read_temperature().await?;
+
+        let closest = table
+            .iter()
+            .min_by_key(|(lut_temp, _)| (*lut_temp as i16 - temp as i16).unsigned_abs());
+        if let Some((_, lut)) = closest {
+            self.load_custom_lut(lut).await?;
+        }
+        Ok(temp)
+    }
+
+    /// Fails with [`DisplayError::TooCold`] if `min_refresh_temp_c` is set and the panel is
+    /// colder.
+    async fn check_temperature(&mut self) -> Result<(), DisplayError> {
+        let Some(min) = self.config.min_refresh_temp_c else {
+            return Ok(());
+        };
+
+        let temp = self.read_temperature().await?;
+        if temp < min {
+            return Err(DisplayError::TooCold { temp, min });
+        }
+        Ok(())
+    }
+
+    /// Programs the VCOM register from a voltage in millivolts, in -25mV steps (-200 to -3000).
+    pub async fn set_vcom(&mut self, millivolts: i16) -> Result<(), DisplayError> {
+        let register = (-(millivolts as i32) / 25).clamp(0, u8::MAX as i32) as u8;
+        self.send_command(WRITE_VCOM, Some(&[register])).await
+    }
+
+    /// Sets the number of dummy line periods (`A[6:0]`, 0–127) inserted per frame. Higher bits
+    /// are ignored.
+    pub async fn set_dummy_line_period(&mut self, period: u8) -> Result<(), DisplayError> {
+        self.send_command(SET_DUMMY_LINE_PERIOD, Some(&[period & 0x7f]))
+            .await
+    }
+
+    /// Sets the gate line width (`A[3:0]`, 0–15), which scales the time spent driving each gate
+    /// line. Higher bits are ignored.
+    pub async fn set_gate_line_width(&mut self, width: u8) -> Result<(), DisplayError> {
+        self.send_command(SET_GATE_LINE_WIDTH, Some(&[width & 0x0f]))
+            .await
+    }
+
+    /// Sets the first gate line driven during a refresh (`0x0F`, 9 bits).
+    pub async fn set_gate_scan_start(&mut self, line: u16) -> Result<(), DisplayError> {
+        self.send_command(SET_GATE_SCAN_START, Some(&gate_address(line as usize)))
+            .await
+    }
+
+    /// Switches the gate scan order, keeping the configured scan direction. Takes effect with the
+    /// next refresh and is kept across resets.
+    pub async fn set_scan_mode(&mut self, mode: ScanMode) -> Result<(), DisplayError> {
+        self.config.scan_mode = mode;
+        self.set_driver_output_control().await
+    }
+
+    /// Writes driver output control (`0x01`): the number of gate lines in use, and the scan
+    /// order and direction bits from the config.
+    async fn set_driver_output_control(&mut self) -> Result<(), DisplayError> {
+        let [mux_low, mux_high] = gate_address(Y - 1 + self.config.visible_y_offset);
+        let mut scan = 0x00;
+        if self.config.scan_mode == ScanMode::Interlaced {
+            scan |= 0x02;
+        }
+        if self.config.reverse_gate_scan {
+            scan |= 0x01;
+        }
+        self.send_command(DRIVER_OUTPUT_CONTROL, Some(&[mux_low, mux_high, scan]))
+            .await
+    }
+
+    /// Sets the border waveform control byte (`0x3C`), which selects what the panel border is
+    /// driven to during a refresh.
+    pub async fn set_border_waveform(&mut self, waveform: u8) -> Result<(), DisplayError> {
+        self.send_command(SET_BORDER_WAVEFORM, Some(&[waveform]))
+            .await
+    }
+
+    /// Sets the gate driving voltage (`A[4:0]`). Higher bits are ignored.
+    pub async fn set_gate_voltage(&mut self, voltage: u8) -> Result<(), DisplayError> {
+        self.send_command(SET_GATE_VOLTAGE, Some(&[voltage & 0x1f]))
+            .await
+    }
+
+    /// Sets the source driving voltages VSH1, VSH2 and VSL.
+    pub async fn set_source_voltage(&mut self, voltages: [u8; 3]) -> Result<(), DisplayError> {
+        self.send_command(SET_SOURCE_VOLTAGE, Some(&voltages)).await
+    }
+
+    /// Writes a full frame into one RAM bank without refreshing the panel. Fails with
+    /// [`DisplayError::BufferSizeMismatch`] unless `buffer` is exactly `X * Y / 8` bytes.
+    pub async fn write_ram(&mut self, plane: RamPlane, buffer: &[u8]) -> Result<(), DisplayError> {
+        check_frame_len::<X, Y>(buffer)?;
+        self.set_ram_x(0, (X - 1) / 8).await?;
+        self.set_ram_y(0, Y - 1).await?;
+        self.set_pointer(0, 0).await?;
+        self.write_scanlines(plane.write_command(&self.config.commands), buffer)
+            .await
+    }
+
+    /// Sends whole scanlines of `data` with the RAM write `command`, each reversed if
+    /// `reverse_scanline_bytes` is set.
+    async fn write_scanlines(&mut self, command: u8, data: &[u8]) -> Result<(), DisplayError> {
+        if self.config.reverse_scanline_bytes {
+            self.write_scanlines_with(command, data.len(), &|index| data[index])
+                .await
+        } else {
+            self.send_command(command, Some(data)).await
+        }
+    }
+
+    /// Like [`Self::write_scanlines`], for `len` bytes generated from their buffer index by
+    /// `byte` rather than held in memory.
+    async fn write_scanlines_with(
+        &mut self,
+        command: u8,
+        len: usize,
+        byte: &dyn Fn(usize) -> u8,
+    ) -> Result<(), DisplayError> {
+        let columns = X / 8;
+        let reverse = self.config.reverse_scanline_bytes;
+        let source = move |index: usize| {
+            if reverse {
+                index - index % columns + columns - 1 - index % columns
+            } else {
+                index
+            }
+        };
+        self.send_command_iter(command, (0..len).map(|index| byte(source(index))))
+            .await
+    }
+
+    /// RAM byte columns holding the buffer byte columns `start..=end` of each scanline.
+    fn ram_columns(&self, (start, end): (usize, usize)) -> (usize, usize) {
+        if self.config.reverse_scanline_bytes {
+            let last = (X - 1) / 8;
+            (last - end, last - start)
+        } else {
+            (start, end)
+        }
+    }
+
+    /// Clears the red RAM bank of a tri-color panel and runs a full refresh, removing all red
+    /// content. The black/white bank isn't written, so the panel shows what it last held.
+    pub async fn clear_red(&mut self) -> Result<(), DisplayError> {
+        self.wait_for_idle().await?;
+        self.check_temperature().await?;
+        self.set_ram_x(0, (X - 1) / 8).await?;
+        self.set_ram_y(0, Y - 1).await?;
+        self.set_pointer(0, 0).await?;
+        self.fill_ram(self.config.commands.write_ram_red, 0x00)
+            .await?;
+        self.full_refresh().await
+    }
+
+    /// Factory self-test: shows all black, all white, a checkerboard of 8 pixel squares and
+    /// black corner markers on white, each with a full refresh followed by a `pause_ms` pause for
+    /// inspecting the panel. Costs four full refreshes (about 8 s on the SSD1681) plus the
+    /// pauses, and leaves the corner markers on screen.
+    pub async fn self_test(&mut self, pause_ms: u32) -> Result<(), DisplayError> {
+        let bytes_per_scan = X / 8;
+        let checkerboard = |index: usize| {
+            let (column, row) = (index % bytes_per_scan, index / bytes_per_scan);
+            if (column + row / 8) % 2 == 0 {
+                0x00
+            } else {
+                0xff
+            }
+        };
+        let corners = |index: usize| {
+            let (column, row) = (index % bytes_per_scan, index / bytes_per_scan);
+            let edge_column = column == 0 || column == bytes_per_scan - 1;
+            let edge_row = row < 8 || row + 8 >= Y;
+            if edge_column && edge_row { 0x00 } else { 0xff }
+        };
+
+        let patterns: [&dyn Fn(usize) -> u8; 4] = [&|_| 0x00, &|_| 0xff, &checkerboard, &corners];
+        for pattern in patterns {
+            self.draw_pattern(pattern).await?;
+            self.delay.delay_ms(pause_ms).await;
+        }
+        Ok(())
+    }
+
+    /// Like drawing a frame whose byte at each index is `pattern(index)`, without needing the
+    /// frame in memory.
+    async fn draw_pattern(&mut self, pattern: &dyn Fn(usize) -> u8) -> Result<(), DisplayError> {
+        self.wait_for_idle().await?;
+        self.check_temperature().await?;
+        self.partial_refreshes = 0;
+        self.sync_red_ram().await?;
+        if let Some(retained) = self.retained_frame.as_deref_mut() {
+            for (index, byte) in retained.iter_mut().enumerate() {
+                *byte = pattern(index);
+            }
+        }
+
+        self.write_ram_with(RamPlane::BlackWhite, pattern).await?;
+        self.full_refresh().await?;
+
+        if self.config.keep_shown_frame_in_red_ram {
+            self.write_ram_with(RamPlane::Red, pattern).await?;
+        }
+        Ok(())
+    }
+
+    async fn write_ram_with(
+        &mut self,
+        plane: RamPlane,
+        pattern: &dyn Fn(usize) -> u8,
+    ) -> Result<(), DisplayError> {
+        self.set_ram_x(0, (X - 1) / 8).await?;
+        self.set_ram_y(0, Y - 1).await?;
+        self.set_pointer(0, 0).await?;
+        let command = plane.write_command(&self.config.commands);
+        self.write_scanlines_with(command, buffer_len(X, Y), pattern)
+            .await
+    }
+
+    /// Flashes `area` of a tri-color panel solid red and back `times` times, pausing
+    /// `interval_ms` after each refresh, and leaves it without red.
+    ///
+    /// This is the fastest the SSD1681 can toggle red: only the window covering `area`, widened
+    /// to whole bytes, is rewritten, but the OTP has no partial waveform for the red particles,
+    /// so every toggle still runs a full tri-color refresh of the whole panel, typically 10–15 s.
+    /// Red content previously in the area is lost; the black/white bank isn't touched.
+    pub async fn flash_red_region(
+        &mut self,
+        area: Rectangle,
+        times: u8,
+        interval_ms: u32,
+    ) -> Result<(), DisplayError> {
+        let area = area.intersection(&Rectangle::new(
+            Point::zero(),
+            Size::new(X as u32, Y as u32),
+        ));
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+        let x_bytes = (area.top_left.x as usize / 8, bottom_right.x as usize / 8);
+        let y_lines = (area.top_left.y as usize, bottom_right.y as usize);
+        let ram_x = self.ram_columns(x_bytes);
+        let len = (x_bytes.1 - x_bytes.0 + 1) * (y_lines.1 - y_lines.0 + 1);
+
+        self.wait_for_idle().await?;
+        self.check_temperature().await?;
+        self.set_ram_x(ram_x.0, ram_x.1).await?;
+        self.set_ram_y(y_lines.0, y_lines.1).await?;
+
+        let flashed = async {
+            for _ in 0..times {
+                for red in [0xff, 0x00] {
+                    self.set_pointer(ram_x.0, y_lines.0).await?;
+                    let fill = core::iter::repeat_n(red, len);
+                    self.send_command_iter(self.config.commands.write_ram_red, fill)
+                        .await?;
+                    self.full_refresh().await?;
+                    self.delay.delay_ms(interval_ms).await;
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        self.set_ram_x(0, (X - 1) / 8).await?;
+        self.set_ram_y(0, Y - 1).await?;
+        flashed
+    }
+
+    /// Runs a full refresh from whatever is currently in RAM.
+    pub async fn refresh(&mut self) -> Result<(), DisplayError> {
+        self.check_temperature().await?;
+        self.full_refresh().await
+    }
+
+    async fn full_refresh(&mut self) -> Result<(), DisplayError> {
+        self.set_update_sequence().await?;
+        self.refresh_activation().await
+    }
+
+    /// Loads the temperature and waveform LUT without displaying anything, so a following
+    /// [`Self::refresh_preloaded`] only pays for the display phase. Lets the cost of a full
+    /// refresh be split across two quieter moments. Does nothing while
+    /// [`Self::use_register_lut`] is enabled.
+    pub async fn preload_lut(&mut self) -> Result<(), DisplayError> {
+        if self.register_lut {
+            return Ok(());
+        }
+        self.set_display_update_control(UpdateSequence::PRELOAD_LUT)
+            .await?;
+        self.master_activation().await
+    }
+
+    /// Runs only the display phase of a full refresh, using the LUT loaded by
+    /// [`Self::preload_lut`].
+    pub async fn refresh_preloaded(&mut self) -> Result<(), DisplayError> {
+        self.check_temperature().await?;
+        self.set_display_update_control(UpdateSequence::DISPLAY_ONLY)
+            .await?;
+        self.master_activation().await
+    }
+
+    /// Draws a frame without a frame buffer, for MCUs that can't spare `X * Y / 8` bytes, then
+    /// runs a full refresh.
+    ///
+    /// `draw` is called once per band of `B / (X / 8)` scanlines with a [`Page`] holding just that
+    /// band, and each band is written to RAM before the next is drawn. The scene is rendered from
+    /// scratch for every band, so a 200-line panel drawn through a 200 byte page (8 lines) pays
+    /// for 25 renders; a larger `B` trades RAM for fewer. Pages start out black, like a zeroed
+    /// [`BufferedDisplay`](crate::graphics::BufferedDisplay). Frames drawn this way aren't kept
+    /// for [`Self::retain_frames_in`]. Fails with [`DisplayError::BufferSizeMismatch`] if `B`
+    /// can't hold a single scanline.
+    pub async fn draw_paged<const B: usize>(
+        &mut self,
+        rotation: Rotation,
+        mut draw: impl FnMut(&mut Page<B>),
+    ) -> Result<(), DisplayError> {
+        let mut page = Page::<B>::new(rotation, X, Y);
+        if page.rows() == 0 {
+            return Err(DisplayError::BufferSizeMismatch);
+        }
+
+        self.wait_for_idle().await?;
+        self.check_temperature().await?;
+        self.partial_refreshes = 0;
+
+        for first_row in (0..Y).step_by(page.rows()) {
+            page.start(first_row);
+            draw(&mut page);
+
+            let band = page.band();
+            self.set_ram_y(band.start, band.end - 1).await?;
+            self.set_pointer(0, band.start).await?;
+            self.write_scanlines(self.config.commands.write_ram, page.bytes())
+                .await?;
+        }
+
+        self.set_ram_y(0, Y - 1).await?;
+        self.full_refresh().await
+    }
+
+    /// Shows `frames` one after the other as a short animation.
+    ///
+    /// The analog supplies are powered up and the LUT loaded once, then stay on across the
+    /// sequence; each frame only runs the display phase. They are powered down again at the end,
+    /// even if a frame fails.
+    pub async fn play<'f>(
+        &mut self,
+        frames: impl IntoIterator<Item = &'f [u8]>,
+        mode: UpdateMode,
+    ) -> Result<(), DisplayError> {
+        self.wait_for_idle().await?;
+        self.check_temperature().await?;
+
+        let played = self.play_frames(frames, mode).await;
+        let powered_off = async {
+            self.set_display_update_control(UpdateSequence::POWER_OFF)
+                .await?;
+            self.master_activation().await
+        }
+        .await;
+        // a failed frame is the error worth reporting, even if powering down failed as well
+        played.and(powered_off)
+    }
+
+    async fn play_frames<'f>(
+        &mut self,
+        frames: impl IntoIterator<Item = &'f [u8]>,
+        mode: UpdateMode,
+    ) -> Result<(), DisplayError> {
+        // clock and analog on, load temperature and the LUT for the selected display mode
+        let power_up = if mode == UpdateMode::CursorBlink {
+            self.load_cursor_blink_lut().await?;
+            UpdateSequence::POWER_ON
+        } else if self.register_lut {
+            UpdateSequence::POWER_ON
+        } else {
+            UpdateSequence {
+                load_temperature: true,
+                load_lut: true,
+                ..UpdateSequence::POWER_ON
+            }
+            .with_mode(mode)
+        };
+        self.set_display_update_control(power_up).await?;
+        self.master_activation().await?;
+
+        for frame in frames {
+            self.write_ram(RamPlane::BlackWhite, frame).await?;
+            let display = UpdateSequence {
+                display: true,
+                ..UpdateSequence::POWER_ON
+            };
+            self.set_display_update_control(display.with_mode(mode))
+                .await?;
+            self.master_activation().await?;
+            if mode != UpdateMode::Full || self.config.keep_shown_frame_in_red_ram {
+                self.write_ram(RamPlane::Red, frame).await?;
+            }
+            self.retain_frame(frame);
+        }
+        Ok(())
+    }
+
+    /// Shows the change to `area` of `buffer`, a whole frame, with [`UpdateMode::CursorBlink`]:
+    /// like [`DisplayDriver::draw_partial`], without any flashing but with heavy ghosting. A LUT
+    /// from [`Self::load_custom_lut`] is replaced; later refreshes use the OTP one again.
+    pub async fn draw_cursor_blink(
+        &mut self,
+        buffer: &[u8],
+        area: Rectangle,
+    ) -> Result<(), DisplayError> {
+        if self.full_refresh_due(area) {
+            return self.draw_frame(buffer).await;
+        }
+        self.draw_window(buffer, area, UpdateMode::CursorBlink, true)
+            .await
+    }
+
+    /// Wipes from `from`, the frame the panel shows, to `to`, left to right in `steps` partial
+    /// updates of a vertical strip each. Takes about `steps` partial refreshes, roughly 0.3 s
+    /// each on the SSD1681, where a single full refresh takes about 2 s. The steps bypass
+    /// `full_refresh_interval`: none is promoted to a full refresh, and they don't count towards
+    /// the next promotion.
+    pub async fn transition(
+        &mut self,
+        from: &[u8],
+        to: &[u8],
+        steps: u8,
+    ) -> Result<(), DisplayError> {
+        check_frame_len::<X, Y>(from)?;
+        check_frame_len::<X, Y>(to)?;
+        self.wait_for_idle().await?;
+
+        // the partial waveform drives the difference to the red bank, which has to hold `from`
+        self.write_ram(RamPlane::Red, from).await?;
+        self.red_ram_stale = false;
+
+        let columns = X / 8;
+        let steps = usize::from(steps.max(1));
+        for step in 0..steps {
+            let (start, end) = (step * columns / steps, (step + 1) * columns / steps);
+            if start == end {
+                continue;
+            }
+            let strip = Rectangle::new(
+                Point::new(start as i32 * 8, 0),
+                Size::new((end - start) as u32 * 8, Y as u32),
+            );
+            self.draw_window(to, strip, UpdateMode::Partial, true)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn load_cursor_blink_lut(&mut self) -> Result<(), DisplayError> {
+        self.send_command(WRITE_LUT, Some(&CURSOR_BLINK_LUT))
+            .await?;
+        // the OTP waveform has to be reloaded by the next regular refresh
+        self.register_lut = false;
+        Ok(())
+    }
+
+    /// Counts a partial update of `area` towards `full_refresh_interval`, returning whether it
+    /// is the one to be drawn as a full refresh instead. Updates of nothing visible don't count.
+    fn full_refresh_due(&mut self, area: Rectangle) -> bool {
+        let panel = Rectangle::new(Point::zero(), Size::new(X as u32, Y as u32));
+        if self.config.full_refresh_interval == 0 || area.intersection(&panel).is_zero_sized() {
+            return false;
+        }
+        self.partial_refreshes += 1;
+        self.partial_refreshes >= self.config.full_refresh_interval
+    }
+
+    /// Writes the `area` window of `buffer` and refreshes it with the partial or cursor blink
+    /// waveform. With `mirror_red` the window is copied to the red bank as well, for the next
+    /// partial update to diff against; without it that bank isn't touched.
+    async fn draw_window(
+        &mut self,
+        buffer: &[u8],
+        area: Rectangle,
+        mode: UpdateMode,
+        mirror_red: bool,
+    ) -> Result<(), DisplayError> {
+        check_frame_len::<X, Y>(buffer)?;
+        let area = area.intersection(&Rectangle::new(
+            Point::zero(),
+            Size::new(X as u32, Y as u32),
+        ));
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        self.wait_for_idle().await?;
+        self.check_temperature().await?;
+        if mirror_red {
+            self.sync_red_ram().await?;
+        }
+        self.retain_frame(buffer);
+
+        let x_bytes = (area.top_left.x as usize / 8, bottom_right.x as usize / 8);
+        let y_lines = (area.top_left.y as usize, bottom_right.y as usize);
+        let ram_x = self.ram_columns(x_bytes);
+
+        self.set_ram_x(ram_x.0, ram_x.1).await?;
+        self.set_ram_y(y_lines.0, y_lines.1).await?;
+        self.set_pointer(ram_x.0, y_lines.0).await?;
+        self.write_window(self.config.commands.write_ram, buffer, x_bytes, y_lines)
+            .await?;
+        if mode == UpdateMode::CursorBlink {
+            self.load_cursor_blink_lut().await?;
+            let sequence = UpdateSequence::DISPLAY_ONLY.with_mode(UpdateMode::Partial);
+            self.set_display_update_control(sequence).await?;
+        } else {
+            self.set_partial_update_sequence().await?;
+        }
+        self.refresh_activation().await?;
+
+        if mirror_red {
+            // display mode 2 diffs against the red RAM bank, so keep it in step with what's shown
+            self.set_pointer(ram_x.0, y_lines.0).await?;
+            self.write_window(self.config.commands.write_ram_red, buffer, x_bytes, y_lines)
+                .await?;
+        }
+
+        self.set_ram_x(0, (X - 1) / 8).await?;
+        self.set_ram_y(0, Y - 1).await
+    }
+
+    /// Sends `command` with a payload produced by `data`, e.g. RAM content computed on the fly,
+    /// streamed in batches of 32 bytes so it never has to exist as a whole slice, then waits for
+    /// BUSY like any other command. The `trace` hook reports the iterator's lower size bound as
+    /// `data_len`.
+    pub async fn send_command_iter(
+        &mut self,
+        command: u8,
+        data: impl IntoIterator<Item = u8>,
+    ) -> Result<(), DisplayError> {
+        let data = data.into_iter();
+        self.start_command(command, data.size_hint().0).await?;
+        self.start_data().await?;
+        self.ram_transfer(command, true);
+        let sent = self.send_batches(data).await;
+        self.ram_transfer(command, false);
+        sent?;
+        self.wait_while_busy().await?;
+        self.end_ram_access(command).await
+    }
+
+    /// Sends a NOP (`0x7F`), which does nothing but can serve as a keepalive.
+    pub async fn nop(&mut self) -> Result<(), DisplayError> {
+        self.send_command(NOP, None).await
+    }
 
-    async fn draw_frame(&mut self, buffer: &[u8]) -> Result<(), Self::Error>;
-    async fn enable_backlight(&mut self) -> Result<(), Self::Error>;
-    async fn disable_backlight(&mut self) -> Result<(), Self::Error>;
-}
+    /// Moves the RAM address counter (`0x4E`/`0x4F`) to byte column `x` (pixels `8 * x` to
+    /// `8 * x + 7`) of gate line `y`, for composing RAM writes with [`Self::send_command_iter`].
+    /// Coordinates are relative to the visible area and fail with [`DisplayError::OutOfBounds`]
+    /// outside it.
+    ///
+    /// The driver's data entry mode (`0x03`) increments X first: each written byte advances one
+    /// column, and after the last column of the RAM window the counter wraps to the first one
+    /// and moves down a line. The counter isn't readable; the driver's own writes move it freely.
+    pub async fn set_address_counter(&mut self, x: u16, y: u16) -> Result<(), DisplayError> {
+        let (x, y) = (usize::from(x), usize::from(y));
+        if x >= X / 8 || y >= Y {
+            return Err(DisplayError::OutOfBounds);
+        }
+        self.set_pointer(x, y).await
+    }
 
-pub struct Ssd1681<const X: usize, const Y: usize, SPI, BUSY, DC, BL, RST, DELAY>
-where
-    SPI: SpiDevice,
-    BUSY: InputPin + Wait,
-    DC: OutputPin,
-    BL: OutputPin,
-    RST: OutputPin,
-    DELAY: DelayNs,
-{
-    spi: SPI,
-    busy_pin: BUSY,
-    dc_pin: DC,
-    backlight_pin: Option<BL>,
-    reset_pin: RST,
-    delay: DELAY,
-    config: Ssd1681Config<X, Y>,
-}
+    /// Waits until BUSY goes low after [`DisplayDriver::start_frame`], without the settle delay
+    /// of the internal waits. With [`BusyStrategy::HardwarePin`] this only awaits the pin's
+    /// falling edge, so an interrupt-driven executor can sleep through the refresh;
+    /// [`BusyStrategy::Poll`] polls instead, and [`BusyStrategy::FixedDelay`] returns at once, as
+    /// `start_frame` already waited the refresh out. Honours `busy_timeout_ms`.
+    pub async fn wait_for_refresh_complete(&mut self) -> Result<(), DisplayError> {
+        if let BusyStrategy::FixedDelay(_) = self.config.busy_strategy {
+            return Ok(());
+        }
+        self.wait_for_busy_low().await
+    }
 
-impl<
-    const X: usize,
-    const Y: usize,
-    SPI: SpiDevice,
-    BUSY: InputPin + Wait,
-    DC: OutputPin,
-    BL: OutputPin,
-    RST: OutputPin,
-    DELAY: DelayNs,
-> Ssd1681<X, Y, SPI, BUSY, DC, BL, RST, DELAY>
-{
-    pub async fn new(
-        spi: SPI,
-        busy_pin: BUSY,
-        dc_pin: DC,
-        reset_pin: RST,
-        backlight_pin: Option<BL>,
-        delay: DELAY,
-        config: Ssd1681Config<X, Y>,
-    ) -> Result<Self, DisplayError> {
-        let mut new = Self {
-            spi,
-            busy_pin,
-            dc_pin,
-            backlight_pin,
-            reset_pin,
-            delay,
-            config,
-        };
+    /// Selects the phases run by the next [`Self::master_activation`] (display update control 2,
+    /// `0x22`), e.g. [`UpdateSequence::FULL`] for a full refresh.
+    pub async fn set_display_update_control(
+        &mut self,
+        sequence: UpdateSequence,
+    ) -> Result<(), DisplayError> {
+        self.send_command(
+            self.config.commands.display_update_control,
+            Some(&[sequence.bits()]),
+        )
+        .await
+    }
 
-        new.init().await?;
+    /// Runs the update sequence selected by [`Self::set_display_update_control`] and waits for
+    /// it to finish.
+    pub async fn master_activation(&mut self) -> Result<(), DisplayError> {
+        if self.config.wait_for_busy_cycle {
+            self.start_command(self.config.commands.master_activation, 0)
+                .await?;
+            self.wait_for_busy_level(true).await?;
+            self.wait_for_busy_level(false).await?;
+        } else {
+            self.send_command(self.config.commands.master_activation, None)
+                .await?;
+        }
+        self.wait_fixed_delay().await;
+        Ok(())
+    }
 
-        Ok(new)
+    /// How long the last refresh kept BUSY high, in milliseconds. Only measured with
+    /// `measure_refresh_duration` set; `None` until then.
+    pub fn last_refresh_duration_ms(&self) -> Option<u32> {
+        self.last_refresh_ms
+    }
+
+    /// Length in bytes of the largest single SPI write the driver performs, for sizing DMA
+    /// buffers: a whole RAM plane (`X * Y / 8`) or, for small panels, a custom LUT, capped at
+    /// `write_chunk_size` when `yield_during_write` splits large writes.
+    pub fn max_transfer_size(&self) -> usize {
+        let largest = buffer_len(X, Y).max(CURSOR_BLINK_LUT.len());
+        if self.config.yield_during_write {
+            largest.min(self.config.write_chunk_size.max(1))
+        } else {
+            largest
+        }
+    }
+
+    /// [`Self::master_activation`] for a refresh: the backlight is switched on around it when
+    /// `auto_backlight_on_refresh` is set, and its duration measured when
+    /// `measure_refresh_duration` is.
+    async fn refresh_activation(&mut self) -> Result<(), DisplayError> {
+        if !self.config.auto_backlight_on_refresh {
+            return self.timed_master_activation().await;
+        }
+
+        let refreshed = async {
+            self.enable_backlight().await?;
+            self.timed_master_activation().await?;
+            self.delay.delay_ms(self.config.backlight_hold_ms).await;
+            Ok(())
+        }
+        .await;
+        // don't leave the light on after a failed refresh, but report why the refresh failed
+        let disabled = self.disable_backlight().await;
+        refreshed.and(disabled)
+    }
+
+    async fn timed_master_activation(&mut self) -> Result<(), DisplayError> {
+        if !self.config.measure_refresh_duration {
+            return self.master_activation().await;
+        }
+
+        // the delay is the only clock available, so BUSY is polled once per millisecond and the
+        // polls counted; the result is coarse and slightly long
+        self.start_command(self.config.commands.master_activation, 0)
+            .await?;
+        self.delay.delay_ms(self.config.busy_settle_ms).await;
+        let mut elapsed_ms = self.config.busy_settle_ms;
+        if let BusyStrategy::FixedDelay(ms) = self.config.busy_strategy {
+            self.delay.delay_ms(ms).await;
+            elapsed_ms += ms;
+        }
+        while self.busy()? {
+            if self
+                .config
+                .busy_timeout_ms
+                .is_some_and(|timeout| elapsed_ms >= timeout)
+            {
+                return Err(DisplayError::BusyTimeout);
+            }
+            self.delay.delay_ms(1).await;
+            elapsed_ms += 1;
+        }
+
+        self.last_refresh_ms = Some(elapsed_ms);
+        Ok(())
     }
 
     async fn init(&mut self) -> Result<(), DisplayError> {
+        self.power_on().await?;
         self.delay.delay_ms(10).await; // ensure 10ms has passed since powerup
+
+        let mut backoff_ms = self.config.init_backoff_base_ms;
+        for _ in 0..self.config.init_max_retries {
+            match self.try_init().await {
+                Err(DisplayError::BusyTimeout) => {
+                    self.delay.delay_ms(backoff_ms).await;
+                    backoff_ms = backoff_ms.saturating_mul(2);
+                }
+                result => return result,
+            }
+        }
+        self.try_init().await
+    }
+
+    async fn try_init(&mut self) -> Result<(), DisplayError> {
         self.reset().await?;
+        if self.config.check_spi_on_init {
+            self.check_spi().await?;
+        }
+        if self.config.clear_on_init {
+            self.clear_ram().await?;
+            self.full_refresh().await?;
+        }
+        Ok(())
+    }
+
+    /// Applies every register backed by the configuration. The software reset in [`Self::reset`]
+    /// returns them all to their defaults, so it calls this afterwards.
+    async fn apply_registers(&mut self) -> Result<(), DisplayError> {
         self.set_data_entry_mode().await?;
-        self.set_ram_x().await?;
-        self.set_ram_y().await?;
-        self.set_internal_temp_sensor().await?;
+        self.set_ram_x(0, (X - 1) / 8).await?;
+        self.set_ram_y(0, Y - 1).await?;
+        if self.config.use_internal_temp_sensor {
+            self.set_internal_temp_sensor().await?;
+        }
+        if let Some(period) = self.config.dummy_line_period {
+            self.set_dummy_line_period(period).await?;
+        }
+        if let Some(width) = self.config.gate_line_width {
+            self.set_gate_line_width(width).await?;
+        }
+        if self.config.gate_scan_start != 0 {
+            self.set_gate_scan_start(self.config.gate_scan_start)
+                .await?;
+        }
+        if self.config.scan_mode != ScanMode::Sequential || self.config.reverse_gate_scan {
+            self.set_driver_output_control().await?;
+        }
+        if let Some(waveform) = self.config.border_waveform {
+            self.set_border_waveform(waveform).await?;
+        }
+        if let Some(voltage) = self.config.gate_voltage {
+            self.set_gate_voltage(voltage).await?;
+        }
+        if let Some(voltages) = self.config.source_voltage {
+            self.set_source_voltage(voltages).await?;
+        }
         Ok(())
     }
 
+    /// Fills the black/white RAM with white and the red RAM with no red.
+    async fn clear_ram(&mut self) -> Result<(), DisplayError> {
+        self.set_pointer(0, 0).await?;
+        self.fill_ram(self.config.commands.write_ram, 0xff).await?;
+        self.set_pointer(0, 0).await?;
+        self.fill_ram(self.config.commands.write_ram_red, 0x00)
+            .await
+    }
+
+    async fn fill_ram(&mut self, command: u8, value: u8) -> Result<(), DisplayError> {
+        let chunk = [value; 32];
+        let mut remaining = buffer_len(X, Y);
+
+        self.start_command(command, remaining).await?;
+        self.start_data().await?;
+        self.ram_transfer(command, true);
+        let mut sent = Ok(());
+        while remaining > 0 && sent.is_ok() {
+            let len = remaining.min(chunk.len());
+            sent = self.send_spi(&chunk[..len]).await;
+            remaining -= len;
+        }
+        self.ram_transfer(command, false);
+        sent?;
+        self.wait_while_busy().await?;
+        self.end_ram_access(command).await
+    }
+
     async fn wait_while_busy(&mut self) -> Result<(), DisplayError> {
         self.delay.delay_ms(self.config.busy_settle_ms).await;
-        self.busy_pin
-            .wait_for_low()
+        match self.config.busy_strategy {
+            // register writes and RAM transfers finish at once; only refreshes and resets are
+            // waited out, in wait_fixed_delay
+            BusyStrategy::FixedDelay(_) => Ok(()),
+            BusyStrategy::HardwarePin | BusyStrategy::Poll(_) => self.wait_for_busy_low().await,
+        }
+    }
+
+    /// Without a BUSY line there is nothing to poll, so with [`BusyStrategy::FixedDelay`] the
+    /// operations that keep BUSY high for long, a refresh or a reset, are waited out here.
+    async fn wait_fixed_delay(&mut self) {
+        if let BusyStrategy::FixedDelay(ms) = self.config.busy_strategy {
+            self.delay.delay_ms(ms).await;
+        }
+    }
+
+    async fn wait_for_busy_low(&mut self) -> Result<(), DisplayError> {
+        self.wait_for_busy_level(false).await
+    }
+
+    async fn wait_for_busy_level(&mut self, high: bool) -> Result<(), DisplayError> {
+        let mut retries = self.config.busy_read_retries;
+        loop {
+            match self.try_wait_for_busy_level(high).await {
+                // a read error is retried; a timeout means the pin is stuck and is not
+                Err(DisplayError::BusyPinFailure) if retries > 0 => retries -= 1,
+                result => return result,
+            }
+        }
+    }
+
+    async fn try_wait_for_busy_level(&mut self, high: bool) -> Result<(), DisplayError> {
+        let timeout_ms = self.config.busy_timeout_ms;
+        match self.config.busy_strategy {
+            BusyStrategy::HardwarePin => {
+                let delay = &mut self.delay;
+                let timeout = async move {
+                    match timeout_ms {
+                        Some(ms) => delay.delay_ms(ms).await,
+                        None => core::future::pending().await,
+                    }
+                };
+                let result = with_timeout(self.busy_pin.wait_for_level(high), timeout).await;
+                result
+                    .ok_or(DisplayError::BusyTimeout)?
+                    .map_err(|_| DisplayError::BusyPinFailure)
+            }
+            BusyStrategy::Poll(interval_ms) => {
+                // a zero interval would never let the timeout run out
+                let interval_ms = interval_ms.max(1);
+                let mut waited_ms: u32 = 0;
+                while self.busy()? != high {
+                    if timeout_ms.is_some_and(|timeout| waited_ms >= timeout) {
+                        return Err(DisplayError::BusyTimeout);
+                    }
+                    self.delay.delay_ms(interval_ms).await;
+                    waited_ms = waited_ms.saturating_add(interval_ms);
+                }
+                Ok(())
+            }
+            BusyStrategy::FixedDelay(_) => Ok(()),
+        }
+    }
+
+    async fn read_spi(&mut self, buffer: &mut [u8]) -> Result<(), DisplayError> {
+        self.spi
+            .read(buffer)
             .await
-            .map_err(|_| DisplayError::BusyPinFailure)
+            .map_err(|_| DisplayError::SpiFailure)
+    }
+
+    async fn read_command(&mut self, command: u8, buffer: &mut [u8]) -> Result<(), DisplayError> {
+        self.start_command(command, buffer.len()).await?;
+        self.start_data().await?;
+        self.read_spi(buffer).await?;
+        self.wait_while_busy().await
+    }
+
+    /// Makes sure no earlier refresh is still running before a new frame is written.
+    async fn wait_for_idle(&mut self) -> Result<(), DisplayError> {
+        if !self.busy()? {
+            return Ok(());
+        }
+
+        if !self.config.wait_for_refresh {
+            return Err(DisplayError::RefreshInProgress);
+        }
+
+        self.wait_for_busy_low().await
     }
 
     async fn send_spi(&mut self, data: &[u8]) -> Result<(), DisplayError> {
         self.spi
-            .write(data)
+            .transaction(&mut [write_operation(data)])
             .await
             .map_err(|_| DisplayError::SpiFailure)
     }
 
     async fn send_command(&mut self, command: u8, data: Option<&[u8]>) -> Result<(), DisplayError> {
+        self.start_command(command, data.map_or(0, <[u8]>::len))
+            .await?;
+
+        if let Some(buf) = data {
+            self.ram_transfer(command, true);
+            let sent = self.send_data(buf).await;
+            self.ram_transfer(command, false);
+            sent?;
+        }
+        self.wait_while_busy().await?;
+
+        self.end_ram_access(command).await
+    }
+
+    #[cfg(feature = "trace")]
+    fn trace(&self, command: u8, data_len: usize) {
+        if let Some(trace) = self.config.trace {
+            trace(crate::config::CommandTrace {
+                command,
+                name: crate::commands::name(command),
+                data_len,
+            });
+        }
+    }
+
+    /// Sends the bytes of `data` in small batches, once a command's data phase has started.
+    async fn send_batches(
+        &mut self,
+        mut data: impl Iterator<Item = u8>,
+    ) -> Result<(), DisplayError> {
+        let mut batch = [0; 32];
+        loop {
+            let mut len = 0;
+            for (slot, byte) in batch.iter_mut().zip(&mut data) {
+                *slot = byte;
+                len += 1;
+            }
+            if len > 0 {
+                self.send_spi(&batch[..len]).await?;
+            }
+            if len < batch.len() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Terminates a RAM write or read with a NOP, as the datasheet recommends, if `command` is
+    /// one. NOP doesn't raise BUSY, so it isn't waited on.
+    async fn end_ram_access(&mut self, command: u8) -> Result<(), DisplayError> {
+        let commands = &self.config.commands;
+        if ![
+            commands.write_ram,
+            commands.write_ram_red,
+            commands.read_ram,
+        ]
+        .contains(&command)
+        {
+            return Ok(());
+        }
+
+        self.start_command(NOP, 0).await
+    }
+
+    /// Runs the RAM transfer hook if `command` writes a RAM bank.
+    fn ram_transfer(&mut self, command: u8, starting: bool) {
+        let commands = &self.config.commands;
+        if command != commands.write_ram && command != commands.write_ram_red {
+            return;
+        }
+        if let Some(hook) = self.ram_transfer_hook {
+            hook(&mut self.spi, starting);
+        }
+    }
+
+    /// Sends the `command` byte, reporting it to the `trace` hook with the `data_len` bytes that
+    /// will follow it. Every command goes through here.
+    #[cfg_attr(not(feature = "trace"), allow(unused_variables))]
+    async fn start_command(&mut self, command: u8, data_len: usize) -> Result<(), DisplayError> {
         if self.busy()? {
             return Err(DisplayError::DeviceBusy);
         }
+        #[cfg(feature = "trace")]
+        self.trace(command, data_len);
 
         self.dc_pin
             .set_low()
             .map_err(|_| DisplayError::DataCommandPinFailure)?;
+        self.settle_dc().await;
+
+        self.send_spi(&[command]).await
+    }
+
+    async fn start_data(&mut self) -> Result<(), DisplayError> {
+        self.dc_pin
+            .set_high()
+            .map_err(|_| DisplayError::DataCommandPinFailure)?;
+        self.settle_dc().await;
+        Ok(())
+    }
+
+    async fn settle_dc(&mut self) {
+        // a millisecond timer would stretch every sub-millisecond settle to a whole millisecond
+        if self.config.coarse_delay && self.config.dc_settle_us < 1000 {
+            return;
+        }
         self.delay.delay_us(self.config.dc_settle_us).await;
+    }
 
-        self.send_spi(&[command]).await?;
+    async fn send_data(&mut self, data: &[u8]) -> Result<(), DisplayError> {
+        self.start_data().await?;
 
-        if let Some(buf) = data {
-            self.dc_pin
-                .set_high()
-                .map_err(|_| DisplayError::DataCommandPinFailure)?;
-            self.delay.delay_us(self.config.dc_settle_us).await;
-            self.send_spi(buf).await?;
+        let chunk_size = self.config.write_chunk_size.max(1);
+        if self.config.yield_during_write && data.len() > chunk_size {
+            for chunk in data.chunks(chunk_size) {
+                self.send_spi(chunk).await?;
+                YieldNow(false).await;
+            }
+            Ok(())
+        } else {
+            self.send_spi(data).await
         }
+    }
+
+    /// Writes the rows `y_start..=y_end` of `buffer`, limited to the bytes `x_start..=x_end` of
+    /// each row, to the RAM bank selected by `command`.
+    async fn write_window(
+        &mut self,
+        command: u8,
+        buffer: &[u8],
+        (x_start, x_end): (usize, usize),
+        (y_start, y_end): (usize, usize),
+    ) -> Result<(), DisplayError> {
+        let data_len = (x_end - x_start + 1) * (y_end - y_start + 1);
+        self.start_command(command, data_len).await?;
+        self.start_data().await?;
+        self.ram_transfer(command, true);
+        let written = self
+            .write_rows(buffer, (x_start, x_end), (y_start, y_end))
+            .await;
+        self.ram_transfer(command, false);
+        written?;
         self.wait_while_busy().await?;
+        self.end_ram_access(command).await
+    }
 
+    /// Sends the rows of a window once its RAM write command has been started.
+    async fn write_rows(
+        &mut self,
+        buffer: &[u8],
+        (x_start, x_end): (usize, usize),
+        (y_start, y_end): (usize, usize),
+    ) -> Result<(), DisplayError> {
+        const ROWS_PER_TRANSACTION: usize = 16;
+        let bytes_per_scan = X / 8;
+
+        if self.config.reverse_scanline_bytes {
+            let rows = (y_start..=y_end).filter_map(|y| {
+                let row_start = y * bytes_per_scan;
+                buffer.get(row_start + x_start..=row_start + x_end)
+            });
+            return self
+                .send_batches(rows.flat_map(|row| row.iter().rev().copied()))
+                .await;
+        }
+
+        // DC is a GPIO, so it can't change inside a transaction; the rows of a window are all
+        // data though, so batch them to save a CS assert per row
+        let mut y = y_start;
+        while y <= y_end {
+            let mut operations = [const { Operation::Write(&[]) }; ROWS_PER_TRANSACTION];
+            let mut count = 0;
+            while count < ROWS_PER_TRANSACTION && y <= y_end {
+                let row_start = y * bytes_per_scan;
+                if let Some(row) = buffer.get(row_start + x_start..=row_start + x_end) {
+                    operations[count] = write_operation(row);
+                    count += 1;
+                }
+                y += 1;
+            }
+
+            self.spi
+                .transaction(&mut operations[..count])
+                .await
+                .map_err(|_| DisplayError::SpiFailure)?;
+            if self.config.yield_during_write {
+                YieldNow(false).await;
+            }
+        }
         Ok(())
     }
 
@@ -126,13 +1975,30 @@ impl<
         self.send_command(SET_DATA_ENTRY_MODE, Some(&[0x03])).await
     }
 
-    async fn set_ram_x(&mut self) -> Result<(), DisplayError> {
-        self.send_command(SET_RAM_X, Some(&[0x00, ((X - 1) / 8) as u8]))
+    // window and pointer coordinates are relative to the visible area; the configured offsets
+    // map them onto controller RAM
+
+    async fn set_ram_x(&mut self, start: usize, end: usize) -> Result<(), DisplayError> {
+        let offset = self.config.visible_x_offset / 8;
+        let (start, end) = (start + offset, end + offset);
+        self.send_command(SET_RAM_X, Some(&[start as u8, end as u8]))
+            .await
+    }
+
+    async fn set_ram_y(&mut self, start: usize, end: usize) -> Result<(), DisplayError> {
+        let offset = self.config.visible_y_offset;
+        let (start, end) = (start + offset, end + offset);
+        let ([start_low, start_high], [end_low, end_high]) =
+            (gate_address(start), gate_address(end));
+        self.send_command(SET_RAM_Y, Some(&[start_low, start_high, end_low, end_high]))
             .await
     }
 
-    async fn set_ram_y(&mut self) -> Result<(), DisplayError> {
-        self.send_command(SET_RAM_Y, Some(&[0x00, 0x00, (Y - 1) as u8, 0x00]))
+    async fn set_pointer(&mut self, x: usize, y: usize) -> Result<(), DisplayError> {
+        let x = x + self.config.visible_x_offset / 8;
+        let y = y + self.config.visible_y_offset;
+        self.send_command(SET_X_POINTER, Some(&[x as u8])).await?;
+        self.send_command(SET_Y_POINTER, Some(&gate_address(y)))
             .await
     }
 
@@ -142,10 +2008,29 @@ impl<
     }
 
     async fn set_update_sequence(&mut self) -> Result<(), DisplayError> {
-        self.send_command(SET_UPDATE_SEQUENCE, Some(&[0xF7])).await
+        // a custom LUT would be replaced by the OTP one if the sequence loaded it
+        let sequence = if self.register_lut {
+            UpdateSequence::DISPLAY_ONLY
+        } else {
+            UpdateSequence::FULL
+        };
+        self.set_display_update_control(sequence).await
+    }
+
+    async fn set_partial_update_sequence(&mut self) -> Result<(), DisplayError> {
+        let sequence = if self.register_lut {
+            UpdateSequence::DISPLAY_ONLY
+        } else {
+            UpdateSequence::FULL
+        };
+        self.set_display_update_control(sequence.with_mode(UpdateMode::Partial))
+            .await
     }
 
     fn busy(&mut self) -> Result<bool, DisplayError> {
+        if let BusyStrategy::FixedDelay(_) = self.config.busy_strategy {
+            return Ok(false);
+        }
         self.busy_pin
             .is_high()
             .map_err(|_| DisplayError::BusyPinFailure)
@@ -163,10 +2048,15 @@ impl<
             .map_err(|_| DisplayError::ResetPinFailure)?;
 
         self.delay.delay_ms(10).await;
+        // a cold controller can be slow to come out of the reset pulse; wait for it, up to
+        // busy_timeout_ms, rather than failing the software reset as busy
+        self.wait_for_busy_low().await?;
 
         self.send_command(RESET, None).await?;
+        self.wait_fixed_delay().await;
+        self.register_lut = false;
 
-        Ok(())
+        self.apply_registers().await
     }
 }
 
@@ -174,12 +2064,13 @@ impl<
     const X: usize,
     const Y: usize,
     SPI: SpiDevice,
-    BUSY: InputPin + Wait,
+    BUSY: BusyPin,
     DC: OutputPin,
     BL: OutputPin,
     RST: OutputPin,
     DELAY: DelayNs,
-> DisplayDriver for Ssd1681<X, Y, SPI, BUSY, DC, BL, RST, DELAY>
+    PWR: OutputPin,
+> DisplayDriver for Ssd1681<X, Y, SPI, BUSY, DC, BL, RST, DELAY, PWR>
 {
     type Error = DisplayError;
 
@@ -187,12 +2078,125 @@ impl<
     const Y: usize = Y;
 
     async fn draw_frame(&mut self, buffer: &[u8]) -> Result<(), DisplayError> {
-        self.send_command(SET_X_POINTER, Some(&[0x00])).await?;
-        self.send_command(SET_Y_POINTER, Some(&[0x00, 0x00]))
-            .await?;
-        self.send_command(WRITE_RAM, Some(buffer)).await?;
+        check_frame_len::<X, Y>(buffer)?;
+        self.wait_for_idle().await?;
+        self.check_temperature().await?;
+        self.partial_refreshes = 0;
+        self.sync_red_ram().await?;
+        self.retain_frame(buffer);
+        self.write_ram(RamPlane::BlackWhite, buffer).await?;
+        self.full_refresh().await?;
+
+        if self.config.keep_shown_frame_in_red_ram {
+            self.write_ram(RamPlane::Red, buffer).await?;
+        }
+        Ok(())
+    }
+
+    /// Fails with [`DisplayError::FrameMismatch`], without refreshing, if the second write doesn't
+    /// read back either. Needs an SPI bus that can read from the panel.
+    async fn draw_frame_verified(&mut self, buffer: &[u8]) -> Result<(), DisplayError> {
+        check_frame_len::<X, Y>(buffer)?;
+        self.wait_for_idle().await?;
+        self.check_temperature().await?;
+        self.partial_refreshes = 0;
+        self.sync_red_ram().await?;
+        self.write_ram(RamPlane::BlackWhite, buffer).await?;
+        if !self.verify_frame(buffer).await? {
+            self.write_ram(RamPlane::BlackWhite, buffer).await?;
+            if !self.verify_frame(buffer).await? {
+                return Err(DisplayError::FrameMismatch);
+            }
+        }
+        // only retained once it's certain to be shown
+        self.retain_frame(buffer);
+        self.full_refresh().await?;
+
+        if self.config.keep_shown_frame_in_red_ram {
+            self.write_ram(RamPlane::Red, buffer).await?;
+        }
+        Ok(())
+    }
+
+    async fn start_frame(&mut self, buffer: &[u8]) -> Result<(), DisplayError> {
+        check_frame_len::<X, Y>(buffer)?;
+        self.wait_for_idle().await?;
+        self.check_temperature().await?;
+        self.partial_refreshes = 0;
+        self.sync_red_ram().await?;
+        self.retain_frame(buffer);
+        self.write_ram(RamPlane::BlackWhite, buffer).await?;
         self.set_update_sequence().await?;
-        self.send_command(REFRESH_PANEL, None).await
+        // the red bank can't be written until the refresh is done, so catch up on the next frame
+        self.red_ram_stale = self.config.keep_shown_frame_in_red_ram;
+
+        self.start_command(self.config.commands.master_activation, 0)
+            .await?;
+        // give BUSY time to rise, so an immediate poll doesn't see the idle line
+        if self.config.wait_for_busy_cycle {
+            self.wait_for_busy_level(true).await?;
+        } else {
+            self.delay.delay_ms(self.config.busy_settle_ms).await;
+        }
+        self.wait_fixed_delay().await;
+        Ok(())
+    }
+
+    fn poll_frame(&mut self) -> Result<bool, DisplayError> {
+        Ok(!self.busy()?)
+    }
+
+    async fn wait_for_frame(&mut self) -> Result<(), DisplayError> {
+        self.wait_for_refresh_complete().await
+    }
+
+    async fn draw_tri_color_frame(
+        &mut self,
+        black_white: &[u8],
+        red: &[u8],
+    ) -> Result<(), DisplayError> {
+        check_frame_len::<X, Y>(black_white)?;
+        check_frame_len::<X, Y>(red)?;
+        self.wait_for_idle().await?;
+        self.check_temperature().await?;
+        self.retain_frame(black_white);
+        self.retain_red(red);
+        self.write_ram(RamPlane::BlackWhite, black_white).await?;
+        self.write_ram(RamPlane::Red, red).await?;
+        self.full_refresh().await
+    }
+
+    async fn draw_red_frame(
+        &mut self,
+        _black_white: &[u8],
+        red: &[u8],
+    ) -> Result<(), DisplayError> {
+        check_frame_len::<X, Y>(red)?;
+        self.wait_for_idle().await?;
+        self.check_temperature().await?;
+        self.retain_red(red);
+        self.write_ram(RamPlane::Red, red).await?;
+        self.full_refresh().await
+    }
+
+    async fn draw_partial(&mut self, buffer: &[u8], area: Rectangle) -> Result<(), DisplayError> {
+        if self.full_refresh_due(area) {
+            return self.draw_frame(buffer).await;
+        }
+        self.draw_window(buffer, area, UpdateMode::Partial, true)
+            .await
+    }
+
+    async fn draw_partial_black_white(
+        &mut self,
+        buffer: &[u8],
+        area: Rectangle,
+    ) -> Result<(), DisplayError> {
+        if self.full_refresh_due(area) {
+            return self.draw_frame(buffer).await;
+        }
+        self.draw_window(buffer, area, UpdateMode::Partial, false)
+            .await
     }
 
     async fn enable_backlight(&mut self) -> Result<(), DisplayError> {
@@ -211,3 +2215,121 @@ impl<
         Ok(())
     }
 }
+
+/// The black/white frame at the start of a retained buffer, which may hold a red plane after it.
+fn black_white_plane<const X: usize, const Y: usize>(retained: &[u8]) -> &[u8] {
+    &retained[..retained.len().min(buffer_len(X, Y))]
+}
+
+fn check_frame_len<const X: usize, const Y: usize>(buffer: &[u8]) -> Result<(), DisplayError> {
+    if buffer.len() == buffer_len(X, Y) {
+        Ok(())
+    } else {
+        Err(DisplayError::BufferSizeMismatch)
+    }
+}
+
+/// SPI operation sending `data`. With the `spi-transfer` feature this is a transfer with an
+/// empty read buffer, for HALs whose `SpiDevice` only implements transfers.
+fn write_operation(data: &[u8]) -> Operation<'_, u8> {
+    #[cfg(feature = "spi-transfer")]
+    return Operation::Transfer(&mut [], data);
+    #[cfg(not(feature = "spi-transfer"))]
+    return Operation::Write(data);
+}
+
+/// Splits a 9-bit gate address into the low byte and bit 8, as `0x45` and `0x4F` expect.
+fn gate_address(y: usize) -> [u8; 2] {
+    [(y & 0xff) as u8, ((y >> 8) & 0x01) as u8]
+}
+
+/// Quickly checks whether a panel appears to be connected, without running the full init.
+///
+/// Resets the controller and issues a software reset, then watches BUSY for the high-then-low
+/// pulse a live SSD1681 produces. Returns `false` if no pulse is seen within ~100ms or a pin/bus
+/// operation fails. The peripherals are borrowed so they can be handed to [`Ssd1681::new`]
+/// afterwards.
+pub async fn probe<SPI, BUSY, DC, RST, DELAY>(
+    spi: &mut SPI,
+    busy_pin: &mut BUSY,
+    dc_pin: &mut DC,
+    reset_pin: &mut RST,
+    delay: &mut DELAY,
+) -> bool
+where
+    SPI: SpiDevice,
+    BUSY: InputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    const POLL_INTERVAL_US: u32 = 100;
+    const POLLS: u32 = 1000;
+
+    let busy_pulse = async {
+        reset_pin
+            .set_low()
+            .map_err(|_| DisplayError::ResetPinFailure)?;
+        delay.delay_ms(10).await;
+        reset_pin
+            .set_high()
+            .map_err(|_| DisplayError::ResetPinFailure)?;
+        delay.delay_ms(10).await;
+
+        dc_pin
+            .set_low()
+            .map_err(|_| DisplayError::DataCommandPinFailure)?;
+        spi.transaction(&mut [write_operation(&[RESET])])
+            .await
+            .map_err(|_| DisplayError::SpiFailure)?;
+
+        let mut went_high = false;
+        for _ in 0..POLLS {
+            let high = busy_pin
+                .is_high()
+                .map_err(|_| DisplayError::BusyPinFailure)?;
+            if high {
+                went_high = true;
+            } else if went_high {
+                return Ok(true);
+            }
+            delay.delay_us(POLL_INTERVAL_US).await;
+        }
+        Ok::<_, DisplayError>(false)
+    };
+
+    matches!(busy_pulse.await, Ok(true))
+}
+
+/// Runs `future` to completion unless `timeout` finishes first, in which case `None` is returned.
+async fn with_timeout<F: Future>(
+    future: F,
+    timeout: impl Future<Output = ()>,
+) -> Option<F::Output> {
+    let (mut future, mut timeout) = (pin!(future), pin!(timeout));
+
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        timeout.as_mut().poll(cx).map(|()| None)
+    })
+    .await
+}
+
+/// Future that returns `Pending` once, giving the executor a chance to run other tasks.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
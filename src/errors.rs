@@ -5,5 +5,19 @@ pub enum DisplayError {
     DataCommandPinFailure,
     BacklightPinFailure,
     ResetPinFailure,
+    PowerPinFailure,
     DeviceBusy,
+    RefreshInProgress,
+    SpiConfigSuspect,
+    BusyTimeout,
+    BufferSizeMismatch,
+    /// RAM still didn't hold the frame after it was written a second time.
+    FrameMismatch,
+    /// A RAM address outside the panel was requested.
+    OutOfBounds,
+    /// The panel is colder than the configured `min_refresh_temp_c`, so the refresh was skipped.
+    TooCold {
+        temp: i8,
+        min: i8,
+    },
 }
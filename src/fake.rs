@@ -0,0 +1,68 @@
+use crate::driver::DisplayDriver;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use embedded_graphics::primitives::Rectangle;
+
+/// In-memory [`DisplayDriver`] for host-side tests: every frame it is asked to draw is recorded
+/// so rendering code can be checked pixel by pixel without hardware.
+#[derive(Default)]
+pub struct FakeDriver<const X: usize, const Y: usize> {
+    /// Black/white frames in the order they were drawn, including partial updates.
+    pub frames: Vec<Vec<u8>>,
+    /// Red planes passed to [`DisplayDriver::draw_tri_color_frame`] and
+    /// [`DisplayDriver::draw_red_frame`].
+    pub red_frames: Vec<Vec<u8>>,
+    pub backlight: bool,
+}
+
+impl<const X: usize, const Y: usize> FakeDriver<X, Y> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn last_frame(&self) -> Option<&[u8]> {
+        self.frames.last().map(Vec::as_slice)
+    }
+}
+
+impl<const X: usize, const Y: usize> DisplayDriver for FakeDriver<X, Y> {
+    type Error = Infallible;
+
+    const X: usize = X;
+    const Y: usize = Y;
+
+    async fn draw_frame(&mut self, buffer: &[u8]) -> Result<(), Infallible> {
+        self.frames.push(buffer.to_vec());
+        Ok(())
+    }
+
+    async fn draw_partial(&mut self, buffer: &[u8], _area: Rectangle) -> Result<(), Infallible> {
+        self.frames.push(buffer.to_vec());
+        Ok(())
+    }
+
+    async fn draw_tri_color_frame(
+        &mut self,
+        black_white: &[u8],
+        red: &[u8],
+    ) -> Result<(), Infallible> {
+        self.frames.push(black_white.to_vec());
+        self.red_frames.push(red.to_vec());
+        Ok(())
+    }
+
+    async fn draw_red_frame(&mut self, _black_white: &[u8], red: &[u8]) -> Result<(), Infallible> {
+        self.red_frames.push(red.to_vec());
+        Ok(())
+    }
+
+    async fn enable_backlight(&mut self) -> Result<(), Infallible> {
+        self.backlight = true;
+        Ok(())
+    }
+
+    async fn disable_backlight(&mut self) -> Result<(), Infallible> {
+        self.backlight = false;
+        Ok(())
+    }
+}
@@ -1,10 +1,12 @@
-use crate::driver::DisplayDriver;
+use crate::driver::{ColorMode, DisplayDriver, UpdateMode, frame_len};
 use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::ops::Range;
 use embedded_graphics::pixelcolor::BinaryColor;
 use embedded_graphics::prelude::*;
 use embedded_graphics::primitives::Rectangle;
 
-#[derive(Default)]
+#[derive(Clone, Copy, Default)]
 pub enum Rotation {
     #[default]
     _0,
@@ -13,44 +15,717 @@ pub enum Rotation {
     _270,
 }
 
+/// Order in which pixels are packed into each buffer byte.
+///
+/// The SSD1681 expects the leftmost pixel of each byte in the most significant bit, as sent by an
+/// SPI peripheral configured MSB-first. Use [`BitOrder::LsbFirst`] only if your SPI peripheral is
+/// fixed to LSB-first transfers, so the packing cancels out the reversal on the wire.
+#[derive(Clone, Copy, Default)]
+pub enum BitOrder {
+    #[default]
+    MsbFirst,
+    LsbFirst,
+}
+
+/// Arrangement of pixels within each buffer scanline, for buffers whose scanlines aren't laid out
+/// the way the panel expects.
+#[derive(Clone, Copy, Default)]
+pub enum BufferLayout {
+    /// Leftmost pixel in the first byte of the scanline, most significant bit first, the way the
+    /// panel expects it.
+    #[default]
+    StandardMsb,
+    /// Scanlines stored right to left: the leftmost pixel is the least significant bit of the
+    /// last byte, i.e. each scanline of [`Self::StandardMsb`] mirrored. Every flush mirrors the
+    /// scanlines into the panel's order for the write and back afterwards.
+    Reversed,
+}
+
+impl BufferLayout {
+    fn apply([hw_x, hw_y]: [usize; 2], width: usize, layout: BufferLayout) -> [usize; 2] {
+        match layout {
+            BufferLayout::StandardMsb => [hw_x, hw_y],
+            BufferLayout::Reversed => [width - 1 - hw_x, hw_y],
+        }
+    }
+}
+
+/// How a [`Layer`]'s pixels combine with what is already in the buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Blend {
+    /// Every pixel of the layer replaces the one below it.
+    #[default]
+    Overwrite,
+    /// Only `On` pixels are drawn; `Off` pixels are transparent.
+    Or,
+}
+
+/// A pre-rendered piece of the screen, composited by [`BufferedDisplay::compose`].
+///
+/// `data` is packed one bit per pixel, `On` set, MSB first, with each of the `size.height` rows
+/// padded to whole bytes. `rotation` turns the layer within its own bounds before it is placed
+/// with its top-left corner at `position`, in the display's logical coordinates.
+#[derive(Clone, Copy)]
+pub struct Layer<'a> {
+    pub data: &'a [u8],
+    pub size: Size,
+    pub position: Point,
+    pub rotation: Rotation,
+    pub blend: Blend,
+}
+
+impl Layer<'_> {
+    /// Whether the pixel at `point` of the rotated layer is set, or `None` if it's outside the
+    /// layer or its data.
+    fn pixel(&self, point: Point) -> Option<bool> {
+        let (width, height) = (self.size.width as usize, self.size.height as usize);
+        let [x, y] = to_hardware(self.rotation, point, width, height)?;
+
+        self.data
+            .get(y * width.div_ceil(8) + x / 8)
+            .map(|byte| byte & (0b1000_0000 >> (x % 8)) != 0)
+    }
+}
+
+/// A packed frame with rotation already applied, ready to be copied into a [`BufferedDisplay`]
+/// with [`BufferedDisplay::blit`].
+pub struct CachedImage<const N: usize> {
+    buffer: [u8; N],
+}
+
+/// Thresholds used by [`BufferedDisplay::recommend_update_mode`].
+#[derive(Clone, Copy, Debug)]
+pub struct UpdatePolicy {
+    /// Changes covering more than this percentage of the screen get a full refresh, since a
+    /// partial update of a large area ghosts about as badly and saves little time.
+    pub max_partial_area_percent: u8,
+    /// Partial updates allowed in a row before a full refresh clears their ghosting; `0` never
+    /// asks for one on that account.
+    pub max_consecutive_partials: u16,
+}
+
+impl Default for UpdatePolicy {
+    fn default() -> Self {
+        Self {
+            max_partial_area_percent: 50,
+            max_consecutive_partials: 10,
+        }
+    }
+}
+
 pub struct BufferedDisplay<D: DisplayDriver, const N: usize> {
     driver: D,
     buffer: [u8; N],
     rotation: Rotation,
+    bit_order: BitOrder,
+    layout: BufferLayout,
+    dirty: Option<Rectangle>,
+    update_policy: UpdatePolicy,
+    partials: u16,
 }
 
 impl<D: DisplayDriver, const N: usize> BufferedDisplay<D, N> {
     pub fn new(driver: D, buffer: [u8; N], rotation: Rotation) -> Self {
-        const {
-            assert!(
-                N == D::X * D::Y / 8,
-                "unexpected buffer size (expected `X * Y / 8` bytes)"
-            )
-        }
+        const { assert_buffer_len(ColorMode::BlackWhite, D::X, D::Y, N) }
         Self {
             driver,
             buffer,
             rotation,
+            bit_order: BitOrder::default(),
+            layout: BufferLayout::default(),
+            dirty: None,
+            update_policy: UpdatePolicy::default(),
+            partials: 0,
+        }
+    }
+
+    /// Creates a display backed by a zeroed buffer. `N` still has to be named (or inferred from
+    /// the surrounding type) and is checked against [`DisplayDriver::BUFFER_LEN`].
+    pub fn with_driver(driver: D, rotation: Rotation) -> Self {
+        Self::new(driver, [0; N], rotation)
+    }
+
+    pub fn set_bit_order(&mut self, bit_order: BitOrder) {
+        self.bit_order = bit_order;
+    }
+
+    pub fn set_layout(&mut self, layout: BufferLayout) {
+        self.layout = layout;
+    }
+
+    pub fn set_update_policy(&mut self, policy: UpdatePolicy) {
+        self.update_policy = policy;
+    }
+
+    /// Suggests how to refresh a change to the logical area `changed`: [`UpdateMode::Partial`]
+    /// for small changes, [`UpdateMode::Full`] for changes covering much of the screen or once
+    /// too many partial updates have run since the last full refresh.
+    pub fn recommend_update_mode(&self, changed: &Rectangle) -> UpdateMode {
+        let policy = self.update_policy;
+        if policy.max_consecutive_partials != 0 && self.partials >= policy.max_consecutive_partials
+        {
+            return UpdateMode::Full;
+        }
+
+        let changed = changed.intersection(&self.bounding_box()).size;
+        let changed_area = changed.width as u64 * changed.height as u64;
+        let screen_area = (D::X * D::Y) as u64;
+        if changed_area * 100 > screen_area * policy.max_partial_area_percent as u64 {
+            UpdateMode::Full
+        } else {
+            UpdateMode::Partial
         }
     }
 
     pub async fn flush(&mut self) -> Result<(), D::Error> {
-        self.driver.draw_frame(&self.buffer).await
+        self.swap_layout();
+        let flushed = self.driver.draw_frame(&self.buffer).await;
+        self.swap_layout();
+
+        flushed?;
+        self.partials = 0;
+        self.dirty = None;
+        Ok(())
+    }
+
+    /// Like [`Self::flush`], but the frame is read back from RAM and written again if it got
+    /// corrupted on the way, before refreshing; see [`DisplayDriver::draw_frame_verified`].
+    pub async fn flush_verified(&mut self) -> Result<(), D::Error> {
+        self.swap_layout();
+        let flushed = self.driver.draw_frame_verified(&self.buffer).await;
+        self.swap_layout();
+
+        flushed?;
+        self.partials = 0;
+        self.dirty = None;
+        Ok(())
+    }
+
+    /// Full refresh showing the buffer turned by 180°, for content drawn at [`Rotation::_0`] that
+    /// occasionally has to be shown upside down.
+    ///
+    /// Turning a packed frame by 180° reverses its byte order and the bits of every byte, so the
+    /// buffer is flipped in place for the write and flipped back afterwards, rather than paying
+    /// for the rotation on every drawn pixel or keeping a second buffer.
+    pub async fn flush_rotated_180(&mut self) -> Result<(), D::Error> {
+        self.swap_layout();
+        reverse_frame(&mut self.buffer);
+        let flushed = self.driver.draw_frame(&self.buffer).await;
+        reverse_frame(&mut self.buffer);
+        self.swap_layout();
+
+        flushed?;
+        self.partials = 0;
+        self.dirty = None;
+        Ok(())
+    }
+
+    /// Writes the buffer and starts a full refresh, returning as soon as the refresh is running.
+    /// For super-loops that would rather poll [`Self::poll_flush`] than await the refresh.
+    pub async fn start_flush(&mut self) -> Result<(), D::Error> {
+        // the frame is in controller RAM once start_frame returns, so the buffer can be restored
+        // while the refresh runs
+        self.swap_layout();
+        let started = self.driver.start_frame(&self.buffer).await;
+        self.swap_layout();
+
+        started?;
+        self.partials = 0;
+        self.dirty = None;
+        Ok(())
+    }
+
+    /// Returns whether the refresh started by [`Self::start_flush`] has finished.
+    pub fn poll_flush(&mut self) -> Result<bool, D::Error> {
+        self.driver.poll_frame()
+    }
+
+    /// Waits for the refresh started by [`Self::start_flush`] to finish; see
+    /// [`DisplayDriver::wait_for_frame`].
+    pub async fn wait_for_flush(&mut self) -> Result<(), D::Error> {
+        self.driver.wait_for_frame().await
+    }
+
+    /// Partially refreshes the smallest region covering every pixel drawn since the last flush.
+    ///
+    /// This is the recommended way to update dynamic content; use [`Self::flush`] periodically to
+    /// clear the ghosting that partial refreshes accumulate.
+    pub async fn flush_dirty(&mut self) -> Result<(), D::Error> {
+        if let Some(area) = self.dirty {
+            self.draw_partial(area).await?;
+            self.partials = self.partials.saturating_add(1);
+            self.dirty = None;
+        }
+        Ok(())
+    }
+
+    /// Draws pixels as an async source produces them, e.g. samples for a live plot, calling `next`
+    /// until it returns `None`.
+    ///
+    /// With a non-zero `flush_every`, the dirty region is partially refreshed after every
+    /// `flush_every` pixels and once more at the end; `0` leaves flushing to the caller.
+    pub async fn draw_pixels_async(
+        &mut self,
+        mut next: impl AsyncFnMut() -> Option<Pixel<BinaryColor>>,
+        flush_every: usize,
+    ) -> Result<(), D::Error> {
+        let mut drawn = 0;
+        while let Some(pixel) = next().await {
+            let Ok(()) = self.draw_iter(core::iter::once(pixel));
+            drawn += 1;
+
+            if flush_every != 0 && drawn % flush_every == 0 {
+                self.flush_dirty().await?;
+            }
+        }
+
+        if flush_every != 0 {
+            self.flush_dirty().await?;
+        }
+        Ok(())
+    }
+
+    /// Partially refreshes only the bounding box of bytes that differ from `background`, so sparse
+    /// content costs far less SPI traffic than a full frame.
+    ///
+    /// Pixels drawn since the last flush are included too, so content erased back to the
+    /// background is still cleared from the panel.
+    pub async fn flush_trimmed(&mut self, background: BinaryColor) -> Result<(), D::Error> {
+        let background = match background {
+            BinaryColor::Off => 0x00,
+            BinaryColor::On => 0xff,
+        };
+        let bytes_per_scan = D::X / 8;
+
+        let mut bounds: Option<[usize; 4]> = None;
+        for (y, row) in self.buffer.chunks(bytes_per_scan.max(1)).enumerate() {
+            let first = row.iter().position(|&byte| byte != background);
+            let last = row.iter().rposition(|&byte| byte != background);
+            if let (Some(first), Some(last)) = (first, last) {
+                bounds = Some(match bounds {
+                    Some([x0, y0, x1, _]) => [x0.min(first), y0, x1.max(last), y],
+                    None => [first, y, last, y],
+                });
+            }
+        }
+
+        let trimmed = bounds.map(|[x0, y0, x1, y1]| {
+            Rectangle::with_corners(
+                Point::new((x0 * 8) as i32, y0 as i32),
+                Point::new((x1 * 8 + 7) as i32, y1 as i32),
+            )
+        });
+        let area = match (trimmed, self.dirty) {
+            (Some(trimmed), Some(dirty)) => Some(union(trimmed, dirty)),
+            (area, None) | (None, area) => area,
+        };
+
+        if let Some(area) = area {
+            self.draw_partial(area).await?;
+            self.partials = self.partials.saturating_add(1);
+        }
+        self.dirty = None;
+        Ok(())
+    }
+
+    /// Partially refreshes whole hardware scanlines, ignoring rotation. Cheaper than
+    /// [`Self::flush_dirty`] for ticker-style content confined to a few rows of the panel.
+    pub async fn flush_rows(&mut self, rows: Range<u16>) -> Result<(), D::Error> {
+        let area = Rectangle::new(
+            Point::new(0, rows.start as i32),
+            Size::new(D::X as u32, rows.len() as u32),
+        );
+        self.flush_hardware_area(area).await
+    }
+
+    /// Partially refreshes a logical (rotated) rectangle of the display, e.g. a widget's bounds.
+    pub async fn flush_area(&mut self, area: Rectangle) -> Result<(), D::Error> {
+        match self.hardware_area(area) {
+            Some(area) => self.flush_hardware_area(area).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn flush_hardware_area(&mut self, area: Rectangle) -> Result<(), D::Error> {
+        self.draw_partial(area).await?;
+        self.partials = self.partials.saturating_add(1);
+
+        if let Some(dirty) = self.dirty
+            && area.contains(dirty.top_left)
+            && dirty
+                .bottom_right()
+                .is_none_or(|point| area.contains(point))
+        {
+            self.dirty = None;
+        }
+        Ok(())
+    }
+
+    /// Renders `image` once into a standalone packed frame using `rotation`, so it can be
+    /// restored with [`Self::blit`] without repeating the per-pixel transform.
+    pub fn bake<I>(&self, image: &I, rotation: Rotation) -> CachedImage<N>
+    where
+        I: Drawable<Color = BinaryColor>,
+    {
+        let mut canvas = Canvas::<D, N> {
+            buffer: [0; N],
+            rotation,
+            bit_order: self.bit_order,
+            layout: self.layout,
+            _driver: PhantomData,
+        };
+        // drawing into a canvas is infallible
+        let _ = image.draw(&mut canvas);
+
+        CachedImage {
+            buffer: canvas.buffer,
+        }
+    }
+
+    /// Replaces the whole buffer with a previously baked image.
+    pub fn blit(&mut self, image: &CachedImage<N>) {
+        self.buffer = image.buffer;
+        self.mark_all_dirty();
+    }
+
+    /// Combines pre-rendered layers into the buffer, in order, so static pieces don't have to be
+    /// re-rendered every frame. See [`Layer`].
+    pub fn compose(&mut self, layers: &[Layer<'_>]) {
+        for layer in layers {
+            let size = logical_size(
+                layer.rotation,
+                layer.size.width as usize,
+                layer.size.height as usize,
+            );
+            let pixels = Rectangle::new(Point::zero(), size)
+                .points()
+                .filter_map(|point| {
+                    let on = layer.pixel(point)?;
+                    match (layer.blend, on) {
+                        (Blend::Or, false) => None,
+                        (_, on) => Some(Pixel(layer.position + point, BinaryColor::from(on))),
+                    }
+                });
+            let Ok(()) = self.draw_iter(pixels);
+        }
+    }
+
+    /// Sets every logical pixel to `f(x, y)`, like drawing the whole screen pixel by pixel but
+    /// packing a buffer byte at a time. `f` is called in buffer order, which is row by row from
+    /// the top left without rotation.
+    pub fn fill_with<F: FnMut(u32, u32) -> BinaryColor>(&mut self, mut f: F) {
+        let bytes_per_scan = (D::X / 8).max(1);
+        for (index, byte) in self.buffer.iter_mut().enumerate() {
+            let buffer_y = index / bytes_per_scan;
+            let mut packed = 0u8;
+            for bit in 0..8 {
+                let buffer_x = index % bytes_per_scan * 8 + bit;
+                let hw = BufferLayout::apply([buffer_x, buffer_y], D::X, self.layout);
+                let [x, y] = to_logical(self.rotation, hw, D::X, D::Y);
+                if f(x as u32, y as u32).is_on() {
+                    packed |= 0b1000_0000 >> bit;
+                }
+            }
+            *byte = match self.bit_order {
+                BitOrder::MsbFirst => packed,
+                BitOrder::LsbFirst => packed.reverse_bits(),
+            };
+        }
+        self.mark_all_dirty();
+    }
+
+    fn mark_all_dirty(&mut self) {
+        self.dirty = Some(Rectangle::new(
+            Point::zero(),
+            Size::new(D::X as u32, D::Y as u32),
+        ));
+    }
+
+    /// Physical resolution of the panel, ignoring rotation; the [`bounding_box`] is the rotated,
+    /// logical size.
+    ///
+    /// [`bounding_box`]: Dimensions::bounding_box
+    pub fn native_size(&self) -> Size {
+        Size::new(D::X as u32, D::Y as u32)
+    }
+
+    pub fn buffer(&self) -> &[u8; N] {
+        &self.buffer
+    }
+
+    /// Mutable access to the packed buffer. The whole frame is marked dirty, since changes made
+    /// through it can't be tracked.
+    pub fn buffer_mut(&mut self) -> &mut [u8; N] {
+        self.mark_all_dirty();
+        &mut self.buffer
+    }
+
+    /// Returns the color of a logical pixel, or `None` if it's off the display.
+    pub fn pixel(&self, point: Point) -> Option<BinaryColor> {
+        let hw = to_hardware(self.rotation, point, D::X, D::Y)?;
+        let position = BufferLayout::apply(hw, D::X, self.layout);
+        read_pixel(&self.buffer, self.bit_order, position, D::X).map(BinaryColor::from)
+    }
+
+    /// Sets (`on`) or clears the pixel at hardware coordinate `hw_x`, `hw_y` of the panel,
+    /// ignoring rotation; the [`BufferLayout`] decides where in the buffer it is stored. Returns
+    /// `false`, changing nothing, if the coordinate is off the panel.
+    pub fn set_pixel_raw(&mut self, hw_x: usize, hw_y: usize, on: bool) -> bool {
+        if hw_x >= D::X || hw_y >= D::Y {
+            return false;
+        }
+
+        let [x, y] = BufferLayout::apply([hw_x, hw_y], D::X, self.layout);
+        pack_pixel(
+            &mut self.buffer,
+            self.bit_order,
+            [x, y],
+            D::X,
+            BinaryColor::from(on),
+        );
+        self.mark_dirty(Rectangle::new(
+            Point::new(x as i32, y as i32),
+            Size::new(1, 1),
+        ));
+        true
+    }
+
+    /// Writes the buffer as a binary PBM image, as it would appear with the current rotation,
+    /// for previewing layouts on the host.
+    #[cfg(feature = "std")]
+    pub fn write_pbm(&self, out: &mut impl std::io::Write) -> std::io::Result<()> {
+        let size = logical_size(self.rotation, D::X, D::Y);
+        write!(out, "P4\n{} {}\n", size.width, size.height)?;
+
+        let mut row = alloc::vec![0u8; (size.width as usize).div_ceil(8)];
+        for y in 0..size.height as i32 {
+            row.fill(0);
+            for x in 0..size.width as i32 {
+                // the panel shows set bits as white, which PBM encodes as 0
+                if self.pixel(Point::new(x, y)) == Some(BinaryColor::Off) {
+                    row[x as usize / 8] |= 0b1000_0000 >> (x % 8);
+                }
+            }
+            out.write_all(&row)?;
+        }
+
+        Ok(())
+    }
+
+    /// Inverts every pixel in a logical (rotated) rectangle, e.g. to highlight a menu entry.
+    /// Calling it again with the same rectangle restores the original pixels.
+    pub fn invert_region(&mut self, area: Rectangle) {
+        let Some(area) = self.hardware_area(area) else {
+            return;
+        };
+        let Some(bottom_right) = area.bottom_right() else {
+            return;
+        };
+
+        self.update_masked(area, bottom_right, |byte, mask| *byte ^= mask);
+        self.mark_dirty(area);
+    }
+
+    /// Draws `src`, a small bitmap packed like [`Layer::data`], with every pixel scaled up to a
+    /// `scale` by `scale` square whose top-left corner is at `top_left`, set bits `On`. The
+    /// squares are filled a byte at a time, which is much faster than drawing a rectangle per
+    /// pixel for QR codes and icons. Respects rotation and clips to the display.
+    pub fn draw_scaled_bitmap(&mut self, src: &[u8], src_size: Size, scale: u32, top_left: Point) {
+        let (width, height) = (src_size.width as usize, src_size.height as usize);
+        let side = Size::new(scale, scale);
+
+        for y in 0..height {
+            for x in 0..width {
+                let Some(byte) = src.get(y * width.div_ceil(8) + x / 8) else {
+                    return;
+                };
+                let on = byte & (0b1000_0000 >> (x % 8)) != 0;
+
+                let position = top_left + Point::new(x as i32, y as i32) * scale as i32;
+                let Some(area) = self.hardware_area(Rectangle::new(position, side)) else {
+                    continue;
+                };
+                let Some(bottom_right) = area.bottom_right() else {
+                    continue;
+                };
+                self.update_masked(area, bottom_right, |byte, mask| {
+                    if on {
+                        *byte |= mask;
+                    } else {
+                        *byte &= !mask;
+                    }
+                });
+                self.mark_dirty(area);
+            }
+        }
+    }
+
+    /// Calls `update` on every buffer byte the hardware rectangle `area` overlaps, with a mask of
+    /// the bits it covers.
+    fn update_masked(
+        &mut self,
+        area: Rectangle,
+        bottom_right: Point,
+        update: impl Fn(&mut u8, u8),
+    ) {
+        let bytes_per_scan = D::X / 8;
+        let (x_start, x_end) = (area.top_left.x as usize, bottom_right.x as usize);
+
+        for y in area.top_left.y as usize..=bottom_right.y as usize {
+            for column in x_start / 8..=x_end / 8 {
+                // bits of this byte covered by the rectangle, leftmost pixel in the MSB
+                let first = x_start.max(column * 8) - column * 8;
+                let last = x_end.min(column * 8 + 7) - column * 8;
+                let mask = (0xff_u8 >> first) & (0xff_u8 << (7 - last));
+                let mask = match self.bit_order {
+                    BitOrder::MsbFirst => mask,
+                    BitOrder::LsbFirst => mask.reverse_bits(),
+                };
+
+                if let Some(byte) = self.buffer.get_mut(y * bytes_per_scan + column) {
+                    update(byte, mask);
+                }
+            }
+        }
+    }
+
+    /// Maps a logical rectangle to the buffer rectangle it covers, clipped to the display.
+    fn hardware_area(&self, area: Rectangle) -> Option<Rectangle> {
+        let area = area.intersection(&self.bounding_box());
+        let bottom_right = area.bottom_right()?;
+
+        let corners = [area.top_left, bottom_right].map(|point| {
+            let hw = to_hardware(self.rotation, point, D::X, D::Y).unwrap_or([0, 0]);
+            let [x, y] = BufferLayout::apply(hw, D::X, self.layout);
+            Point::new(x as i32, y as i32)
+        });
+        Some(Rectangle::with_corners(corners[0], corners[1]))
+    }
+
+    /// Partially refreshes `area` of the buffer, in the panel's layout.
+    async fn draw_partial(&mut self, area: Rectangle) -> Result<(), D::Error> {
+        let area = match self.layout {
+            BufferLayout::StandardMsb => area,
+            BufferLayout::Reversed => {
+                let right = area.bottom_right().map_or(area.top_left.x, |point| point.x);
+                let left = D::X as i32 - 1 - right;
+                Rectangle::new(Point::new(left, area.top_left.y), area.size)
+            }
+        };
+
+        self.swap_layout();
+        let drawn = self.driver.draw_partial(&self.buffer, area).await;
+        self.swap_layout();
+        drawn
+    }
+
+    /// Mirrors every scanline of a [`BufferLayout::Reversed`] buffer, converting it between that
+    /// layout and the panel's; does nothing for [`BufferLayout::StandardMsb`].
+    fn swap_layout(&mut self) {
+        if let BufferLayout::Reversed = self.layout {
+            for row in self.buffer.chunks_mut((D::X / 8).max(1)) {
+                reverse_frame(row);
+            }
+        }
+    }
+
+    /// Grows the dirty region to cover `area`, given in hardware coordinates.
+    fn mark_dirty(&mut self, area: Rectangle) {
+        self.dirty = Some(match self.dirty {
+            Some(dirty) => union(dirty, area),
+            None => area,
+        });
     }
 }
 
 impl<D: DisplayDriver, const N: usize> Dimensions for BufferedDisplay<D, N> {
     fn bounding_box(&self) -> Rectangle {
-        let size = match self.rotation {
-            Rotation::_0 | Rotation::_180 => Size::new(D::X as u32, D::Y as u32),
-            Rotation::_90 | Rotation::_270 => Size::new(D::Y as u32, D::X as u32),
+        Rectangle::new(Point::new(0, 0), logical_size(self.rotation, D::X, D::Y))
+    }
+}
+
+impl<D: DisplayDriver, const N: usize> DrawTarget for BufferedDisplay<D, N> {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    /// Packs one pixel at a time; text and outlined primitives come through here. Solid areas
+    /// take the faster [`Self::fill_solid`].
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (rotation, layout, bit_order) = (self.rotation, self.layout, self.bit_order);
+
+        // track the drawn bounds locally and grow the dirty region once at the end
+        let mut bounds: Option<[usize; 4]> = None;
+        for Pixel(point, color) in pixels.into_iter() {
+            let Some(hw) = to_hardware(rotation, point, D::X, D::Y) else {
+                continue;
+            };
+            let [x, y] = BufferLayout::apply(hw, D::X, layout);
+
+            if pack_pixel(&mut self.buffer, bit_order, [x, y], D::X, color) {
+                bounds = Some(match bounds {
+                    Some([x0, y0, x1, y1]) => [x0.min(x), y0.min(y), x1.max(x), y1.max(y)],
+                    None => [x, y, x, y],
+                });
+            }
+        }
+
+        if let Some([x0, y0, x1, y1]) = bounds {
+            self.mark_dirty(Rectangle::with_corners(
+                Point::new(x0 as i32, y0 as i32),
+                Point::new(x1 as i32, y1 as i32),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Writes whole buffer bytes wherever `area` covers them, in every rotation and layout, and
+    /// packs only the pixels at its unaligned edges.
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        // rotating and mirroring map a rectangle onto a rectangle, so the area stays a run of
+        // buffer bytes per scanline whatever the rotation and layout
+        let Some(area) = self.hardware_area(*area) else {
+            return Ok(());
+        };
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
         };
 
-        Rectangle::new(Point::new(0, 0), size)
+        self.update_masked(area, bottom_right, |byte, mask| match color {
+            BinaryColor::On => *byte |= mask,
+            BinaryColor::Off => *byte &= !mask,
+        });
+        self.mark_dirty(area);
+        Ok(())
     }
 }
 
-impl<D: DisplayDriver, const N: usize> DrawTarget for BufferedDisplay<D, N> {
+fn reverse_frame(buffer: &mut [u8]) {
+    buffer.reverse();
+    buffer
+        .iter_mut()
+        .for_each(|byte| *byte = byte.reverse_bits());
+}
+
+/// Draw target backing [`BufferedDisplay::bake`], sharing the display's pixel transform.
+struct Canvas<D: DisplayDriver, const N: usize> {
+    buffer: [u8; N],
+    rotation: Rotation,
+    bit_order: BitOrder,
+    layout: BufferLayout,
+    _driver: PhantomData<D>,
+}
+
+impl<D: DisplayDriver, const N: usize> Dimensions for Canvas<D, N> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::new(0, 0), logical_size(self.rotation, D::X, D::Y))
+    }
+}
+
+impl<D: DisplayDriver, const N: usize> DrawTarget for Canvas<D, N> {
     type Color = BinaryColor;
     type Error = Infallible;
 
@@ -58,34 +733,217 @@ impl<D: DisplayDriver, const N: usize> DrawTarget for BufferedDisplay<D, N> {
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
-        let bytes_per_scan = D::X / 8;
+        for Pixel(point, color) in pixels.into_iter() {
+            if let Some(hw) = to_hardware(self.rotation, point, D::X, D::Y) {
+                let position = BufferLayout::apply(hw, D::X, self.layout);
+                pack_pixel(&mut self.buffer, self.bit_order, position, D::X, color);
+            }
+        }
+
+        Ok(())
+    }
+}
 
-        for Pixel(Point { x, y }, color) in pixels.into_iter() {
-            let x = x as usize;
-            let y = y as usize;
+/// Rotates a packed, MSB-first 1bpp image into the hardware layout of an `X` by `Y` panel.
+///
+/// `src` holds the image as it should appear with `rotation` applied, so for `_90` and `_270` it
+/// is `Y` pixels wide and `X` pixels tall, with each row padded to a whole byte. `dst` receives
+/// the packed `X * Y / 8` byte frame expected by [`DisplayDriver::draw_frame`].
+pub fn rotate_buffer<const X: usize, const Y: usize>(
+    src: &[u8],
+    rotation: Rotation,
+    dst: &mut [u8],
+) {
+    let size = logical_size(rotation, X, Y);
+    let src_bytes_per_row = (size.width as usize).div_ceil(8);
 
-            let [hw_x, hw_y] = match self.rotation {
-                Rotation::_0 => [x, y],
-                Rotation::_90 => [D::X - 1 - y, x],
-                Rotation::_180 => [D::X - 1 - x, D::Y - 1 - y],
-                Rotation::_270 => [y, D::Y - 1 - x],
+    for y in 0..size.height as usize {
+        for x in 0..size.width as usize {
+            let Some(byte) = src.get(y * src_bytes_per_row + x / 8) else {
+                continue;
             };
+            let color = BinaryColor::from(byte & (0b1000_0000 >> (x % 8)) != 0);
+            let point = Point::new(x as i32, y as i32);
 
-            let byte_index = hw_y * bytes_per_scan + hw_x / 8;
-            let bit_index = hw_x % 8;
+            if let Some(hw) = to_hardware(rotation, point, X, Y) {
+                pack_pixel(dst, BitOrder::MsbFirst, hw, X, color);
+            }
+        }
+    }
+}
 
-            if let Some(byte) = self.buffer.get_mut(byte_index) {
-                match color {
-                    BinaryColor::Off => {
-                        *byte &= !(0b1000_0000 >> bit_index);
-                    }
-                    BinaryColor::On => {
-                        *byte |= 0b1000_0000 >> bit_index;
-                    }
-                }
+/// Smallest rectangle covering both `a` and `b`.
+pub(crate) fn union(a: Rectangle, b: Rectangle) -> Rectangle {
+    let a_bottom_right = a.bottom_right().unwrap_or(a.top_left);
+    let b_bottom_right = b.bottom_right().unwrap_or(b.top_left);
+
+    Rectangle::with_corners(
+        a.top_left.component_min(b.top_left),
+        a_bottom_right.component_max(b_bottom_right),
+    )
+}
+
+/// Fails const evaluation unless `n` is the packed size of an `x` by `y` panel in `mode`, with a
+/// message spelling out both sizes, e.g. "expected 5000 bytes for 200x200, got 4000".
+#[allow(clippy::panic)] // only ever evaluated at compile time
+pub(crate) const fn assert_buffer_len(mode: ColorMode, x: usize, y: usize, n: usize) {
+    let expected = frame_len(mode, x, y);
+    if n == expected {
+        return;
+    }
+
+    let mut message = ConstMessage::new();
+    message.push_str("unexpected buffer size: expected ");
+    message.push_usize(expected);
+    message.push_str(" bytes for ");
+    message.push_usize(x);
+    message.push_str("x");
+    message.push_usize(y);
+    message.push_str(match mode {
+        ColorMode::BlackWhite => " (`X * Y / 8`), got ",
+        ColorMode::TriColor | ColorMode::Grayscale => " (`2 * X * Y / 8`), got ",
+    });
+    message.push_usize(n);
+
+    match core::str::from_utf8(message.bytes.split_at(message.len).0) {
+        Ok(message) => panic!("{}", message),
+        Err(_) => panic!("unexpected buffer size"),
+    }
+}
+
+/// Fixed-capacity string builder for const panic messages, which can't use `format_args!`.
+struct ConstMessage {
+    bytes: [u8; 96],
+    len: usize,
+}
+
+impl ConstMessage {
+    const fn new() -> Self {
+        Self {
+            bytes: [0; 96],
+            len: 0,
+        }
+    }
+
+    const fn push_str(&mut self, s: &str) {
+        let s = s.as_bytes();
+        let mut i = 0;
+        while i < s.len() && self.len < self.bytes.len() {
+            self.bytes[self.len] = s[i];
+            self.len += 1;
+            i += 1;
+        }
+    }
+
+    const fn push_usize(&mut self, mut value: usize) {
+        let mut digits = [0u8; 20];
+        let mut count = 0;
+        loop {
+            digits[count] = b'0' + (value % 10) as u8;
+            count += 1;
+            value /= 10;
+            if value == 0 {
+                break;
             }
         }
+        while count > 0 && self.len < self.bytes.len() {
+            count -= 1;
+            self.bytes[self.len] = digits[count];
+            self.len += 1;
+        }
+    }
+}
 
-        Ok(())
+pub(crate) fn logical_size(rotation: Rotation, width: usize, height: usize) -> Size {
+    match rotation {
+        Rotation::_0 | Rotation::_180 => Size::new(width as u32, height as u32),
+        Rotation::_90 | Rotation::_270 => Size::new(height as u32, width as u32),
+    }
+}
+
+/// Maps a logical point to hardware coordinates, or `None` if it falls outside the panel.
+pub(crate) fn to_hardware(
+    rotation: Rotation,
+    point: Point,
+    width: usize,
+    height: usize,
+) -> Option<[usize; 2]> {
+    let bounding_box = Rectangle::new(Point::zero(), logical_size(rotation, width, height));
+    if !bounding_box.contains(point) {
+        return None;
     }
+
+    let x = point.x as usize;
+    let y = point.y as usize;
+
+    Some(match rotation {
+        Rotation::_0 => [x, y],
+        Rotation::_90 => [width - 1 - y, x],
+        Rotation::_180 => [width - 1 - x, height - 1 - y],
+        Rotation::_270 => [y, height - 1 - x],
+    })
+}
+
+/// Maps hardware coordinates back to the logical point [`to_hardware`] maps onto them.
+fn to_logical(
+    rotation: Rotation,
+    [hw_x, hw_y]: [usize; 2],
+    width: usize,
+    height: usize,
+) -> [usize; 2] {
+    match rotation {
+        Rotation::_0 => [hw_x, hw_y],
+        Rotation::_90 => [hw_y, width - 1 - hw_x],
+        Rotation::_180 => [width - 1 - hw_x, height - 1 - hw_y],
+        Rotation::_270 => [height - 1 - hw_y, hw_x],
+    }
+}
+
+/// Reads the bit for a hardware pixel, or `None` if it lies outside the buffer.
+pub(crate) fn read_pixel(
+    buffer: &[u8],
+    bit_order: BitOrder,
+    [hw_x, hw_y]: [usize; 2],
+    width: usize,
+) -> Option<bool> {
+    let bytes_per_scan = width / 8;
+    let bit_index = match bit_order {
+        BitOrder::MsbFirst => hw_x % 8,
+        BitOrder::LsbFirst => 7 - hw_x % 8,
+    };
+
+    buffer
+        .get(hw_y * bytes_per_scan + hw_x / 8)
+        .map(|byte| byte & (0b1000_0000 >> bit_index) != 0)
+}
+
+/// Sets or clears the bit for a hardware pixel, returning whether it landed in the buffer.
+pub(crate) fn pack_pixel(
+    buffer: &mut [u8],
+    bit_order: BitOrder,
+    [hw_x, hw_y]: [usize; 2],
+    width: usize,
+    color: BinaryColor,
+) -> bool {
+    let bytes_per_scan = width / 8;
+    let byte_index = hw_y * bytes_per_scan + hw_x / 8;
+    let bit_index = match bit_order {
+        BitOrder::MsbFirst => hw_x % 8,
+        BitOrder::LsbFirst => 7 - hw_x % 8,
+    };
+
+    let Some(byte) = buffer.get_mut(byte_index) else {
+        return false;
+    };
+
+    match color {
+        BinaryColor::Off => {
+            *byte &= !(0b1000_0000 >> bit_index);
+        }
+        BinaryColor::On => {
+            *byte |= 0b1000_0000 >> bit_index;
+        }
+    }
+
+    true
 }
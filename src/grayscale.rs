@@ -0,0 +1,100 @@
+use crate::driver::{ColorMode, DisplayDriver};
+use crate::graphics::{
+    BitOrder, Rotation, assert_buffer_len, logical_size, pack_pixel, read_pixel, to_hardware,
+};
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+/// Buffered 2-bit grayscale draw target, for panels refreshed with a 4-level gray LUT.
+///
+/// Pixels are drawn in `C`, e.g. [`Gray2`] or [`Gray4`](embedded_graphics::pixelcolor::Gray4),
+/// and reduced to the four levels of [`Gray2`]. The level's high bit goes to the black/white
+/// RAM bank and its low bit to the red one, the planes laid out one after the other like
+/// [`TriColorDisplay`](crate::tricolor::TriColorDisplay)'s, so black is `00` and white `11` in
+/// the two banks.
+///
+/// The panel only shows the two middle levels as grays with a waveform that drives each bit
+/// pair differently; load one with
+/// [`Ssd1681::load_custom_lut`](crate::driver::Ssd1681::load_custom_lut) before flushing. The
+/// OTP waveform shows levels 0 and 1 black and 2 and 3 white.
+pub struct GrayscaleDisplay<D: DisplayDriver, const N: usize, C = Gray2> {
+    driver: D,
+    buffer: [u8; N],
+    rotation: Rotation,
+    _color: PhantomData<C>,
+}
+
+impl<D: DisplayDriver, const N: usize, C> GrayscaleDisplay<D, N, C> {
+    pub fn new(driver: D, buffer: [u8; N], rotation: Rotation) -> Self {
+        const { assert_buffer_len(ColorMode::Grayscale, D::X, D::Y, N) }
+        Self {
+            driver,
+            buffer,
+            rotation,
+            _color: PhantomData,
+        }
+    }
+
+    /// Creates a display backed by a zeroed buffer, i.e. all black.
+    pub fn with_driver(driver: D, rotation: Rotation) -> Self {
+        Self::new(driver, [0; N], rotation)
+    }
+
+    /// Writes both planes and runs a full refresh.
+    pub async fn flush(&mut self) -> Result<(), D::Error> {
+        let (high, low) = self.buffer.split_at(N / 2);
+        self.driver.draw_tri_color_frame(high, low).await
+    }
+
+    /// Returns the level of a logical pixel, or `None` if it's off the display.
+    pub fn pixel(&self, point: Point) -> Option<Gray2> {
+        let hw = to_hardware(self.rotation, point, D::X, D::Y)?;
+        let (high, low) = self.buffer.split_at(N / 2);
+        let high = read_pixel(high, BitOrder::MsbFirst, hw, D::X)?;
+        let low = read_pixel(low, BitOrder::MsbFirst, hw, D::X)?;
+        Some(Gray2::new(u8::from(high) << 1 | u8::from(low)))
+    }
+
+    pub fn buffer(&self) -> &[u8; N] {
+        &self.buffer
+    }
+}
+
+impl<D: DisplayDriver, const N: usize, C> Dimensions for GrayscaleDisplay<D, N, C> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::new(0, 0), logical_size(self.rotation, D::X, D::Y))
+    }
+}
+
+impl<D, const N: usize, C> DrawTarget for GrayscaleDisplay<D, N, C>
+where
+    D: DisplayDriver,
+    C: PixelColor + Into<Gray2>,
+{
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (high_plane, low_plane) = self.buffer.split_at_mut(N / 2);
+        for Pixel(point, color) in pixels.into_iter() {
+            let Some(hw) = to_hardware(self.rotation, point, D::X, D::Y) else {
+                continue;
+            };
+
+            let level = color.into().luma();
+            let high = BinaryColor::from(level & 0b10 != 0);
+            let low = BinaryColor::from(level & 0b01 != 0);
+
+            pack_pixel(high_plane, BitOrder::MsbFirst, hw, D::X, high);
+            pack_pixel(low_plane, BitOrder::MsbFirst, hw, D::X, low);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use crate::driver::{ColorMode, DisplayDriver};
+use crate::graphics::{
+    BitOrder, Rotation, assert_buffer_len, logical_size, pack_pixel, read_pixel, to_hardware, union,
+};
+use crate::tricolor::{TriColor, plane_colors};
+use core::convert::Infallible;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+/// Tri-color draw target that picks the cheapest refresh for what changed: a fast black/white
+/// partial update while the red plane is untouched, and a full tri-color refresh once any red
+/// pixel has changed.
+///
+/// The buffer is laid out like [`TriColorDisplay`](crate::tricolor::TriColorDisplay)'s. Partial
+/// updates leave red content on the panel as it was, so a red banner can stay up while the mono
+/// rest of the screen updates quickly.
+pub struct HybridDisplay<D: DisplayDriver, const N: usize> {
+    driver: D,
+    buffer: [u8; N],
+    rotation: Rotation,
+    dirty: Option<Rectangle>,
+    red_dirty: bool,
+}
+
+impl<D: DisplayDriver, const N: usize> HybridDisplay<D, N> {
+    pub fn new(driver: D, buffer: [u8; N], rotation: Rotation) -> Self {
+        const { assert_buffer_len(ColorMode::TriColor, D::X, D::Y, N) }
+        Self {
+            driver,
+            buffer,
+            rotation,
+            dirty: None,
+            red_dirty: false,
+        }
+    }
+
+    /// Creates a display backed by a zeroed buffer, i.e. all black.
+    pub fn with_driver(driver: D, rotation: Rotation) -> Self {
+        Self::new(driver, [0; N], rotation)
+    }
+
+    /// Whether the next [`Self::flush`] will run a full tri-color refresh.
+    pub fn needs_full_refresh(&self) -> bool {
+        self.red_dirty
+    }
+
+    /// Runs a full tri-color refresh if the red plane changed since the last full refresh, and
+    /// otherwise partially refreshes the black/white pixels drawn since the last flush, writing
+    /// only the black/white RAM bank.
+    pub async fn flush(&mut self) -> Result<(), D::Error> {
+        if self.red_dirty {
+            return self.flush_full().await;
+        }
+
+        if let Some(area) = self.dirty {
+            let (black_white, _) = self.buffer.split_at(N / 2);
+            self.driver
+                .draw_partial_black_white(black_white, area)
+                .await?;
+            self.dirty = None;
+        }
+        Ok(())
+    }
+
+    /// Runs a full tri-color refresh regardless of what changed, e.g. periodically to clear the
+    /// ghosting partial updates leave behind.
+    pub async fn flush_full(&mut self) -> Result<(), D::Error> {
+        let (black_white, red) = self.buffer.split_at(N / 2);
+        self.driver.draw_tri_color_frame(black_white, red).await?;
+        self.dirty = None;
+        self.red_dirty = false;
+        Ok(())
+    }
+}
+
+impl<D: DisplayDriver, const N: usize> Dimensions for HybridDisplay<D, N> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::new(0, 0), logical_size(self.rotation, D::X, D::Y))
+    }
+}
+
+impl<D: DisplayDriver, const N: usize> DrawTarget for HybridDisplay<D, N> {
+    type Color = TriColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (black_white_plane, red_plane) = self.buffer.split_at_mut(N / 2);
+        let mut bounds: Option<Rectangle> = None;
+
+        for Pixel(point, color) in pixels.into_iter() {
+            let Some(hw) = to_hardware(self.rotation, point, D::X, D::Y) else {
+                continue;
+            };
+
+            let (black_white, red) = plane_colors(color);
+            let was_red = read_pixel(red_plane, BitOrder::MsbFirst, hw, D::X);
+            self.red_dirty |= was_red != Some(red == BinaryColor::On);
+
+            pack_pixel(black_white_plane, BitOrder::MsbFirst, hw, D::X, black_white);
+            pack_pixel(red_plane, BitOrder::MsbFirst, hw, D::X, red);
+
+            let pixel = Rectangle::new(Point::new(hw[0] as i32, hw[1] as i32), Size::new(1, 1));
+            bounds = Some(bounds.map_or(pixel, |bounds| union(bounds, pixel)));
+        }
+
+        if let Some(bounds) = bounds {
+            self.dirty = Some(self.dirty.map_or(bounds, |dirty| union(dirty, bounds)));
+        }
+        Ok(())
+    }
+}
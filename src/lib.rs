@@ -5,8 +5,27 @@
 #![warn(clippy::panic)]
 #![warn(unsafe_code)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 mod commands;
 pub mod config;
+#[cfg(feature = "console")]
+pub mod console;
+pub mod dither;
 pub mod driver;
 pub mod errors;
+#[cfg(feature = "alloc")]
+pub mod fake;
+#[cfg(feature = "console")]
+mod font;
 pub mod graphics;
+pub mod grayscale;
+pub mod hybrid;
+pub mod paged;
+pub mod record;
+pub mod tiled;
+pub mod tricolor;
+pub mod widgets;
@@ -0,0 +1,91 @@
+use crate::graphics::{BitOrder, Rotation, logical_size, pack_pixel, to_hardware};
+use core::convert::Infallible;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+/// Draw target for one band of scanlines, handed out by
+/// [`Ssd1681::draw_paged`](crate::driver::Ssd1681::draw_paged).
+///
+/// It spans the whole (rotated) display, so the scene is drawn the same way for every band;
+/// pixels outside the current band are dropped.
+pub struct Page<const B: usize> {
+    buffer: [u8; B],
+    rotation: Rotation,
+    width: usize,
+    height: usize,
+    first_row: usize,
+    rows: usize,
+}
+
+impl<const B: usize> Page<B> {
+    pub(crate) fn new(rotation: Rotation, width: usize, height: usize) -> Self {
+        Self {
+            buffer: [0; B],
+            rotation,
+            width,
+            height,
+            first_row: 0,
+            rows: B / (width / 8).max(1),
+        }
+    }
+
+    /// Scanlines held per band.
+    pub(crate) fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Clears the band and moves it to start at hardware row `first_row`.
+    pub(crate) fn start(&mut self, first_row: usize) {
+        self.first_row = first_row;
+        self.buffer.fill(0);
+    }
+
+    /// Packed bytes of the current band, clipped to the bottom of the panel.
+    pub(crate) fn bytes(&self) -> &[u8] {
+        let rows = self.rows.min(self.height - self.first_row);
+        &self.buffer[..rows * (self.width / 8)]
+    }
+
+    /// Hardware rows covered by the current band.
+    pub fn band(&self) -> core::ops::Range<usize> {
+        self.first_row..(self.first_row + self.rows).min(self.height)
+    }
+}
+
+impl<const B: usize> Dimensions for Page<B> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(
+            Point::zero(),
+            logical_size(self.rotation, self.width, self.height),
+        )
+    }
+}
+
+impl<const B: usize> DrawTarget for Page<B> {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels.into_iter() {
+            let Some([hw_x, hw_y]) = to_hardware(self.rotation, point, self.width, self.height)
+            else {
+                continue;
+            };
+            if self.band().contains(&hw_y) {
+                let row = hw_y - self.first_row;
+                pack_pixel(
+                    &mut self.buffer,
+                    BitOrder::MsbFirst,
+                    [hw_x, row],
+                    self.width,
+                    color,
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use crate::config::Ssd1681Config;
+use crate::driver::Ssd1681;
+use crate::errors::DisplayError;
+use core::cell::Cell;
+use core::convert::Infallible;
+use embedded_hal::digital::{self, InputPin, OutputPin};
+use embedded_hal::spi::{self, Operation};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::spi::SpiDevice;
+
+/// Receives the bytes a dry run of the driver would have sent over SPI.
+///
+/// The data following a command may arrive split over several [`Self::data`] calls, e.g. when
+/// `write_chunk_size` splits a RAM write.
+pub trait CommandSink {
+    fn command(&mut self, command: u8);
+    fn data(&mut self, data: &[u8]);
+}
+
+/// Captures the command sequence [`Ssd1681::new`] sends for `config`, without any hardware.
+///
+/// The recording peripherals report BUSY as idle and skip all delays. Data read back from the
+/// panel is all zeros, so a config with `check_spi_on_init` set fails with
+/// [`DisplayError::SpiConfigSuspect`].
+pub async fn record_init<const X: usize, const Y: usize>(
+    config: Ssd1681Config<X, Y>,
+    sink: &mut impl CommandSink,
+) -> Result<(), DisplayError> {
+    let dc_high = Cell::new(false);
+    let spi = RecordingSpi {
+        sink,
+        dc_high: &dc_high,
+    };
+
+    Ssd1681::new(
+        spi,
+        IdlePin,
+        DcPin(&dc_high),
+        IdlePin,
+        None::<IdlePin>,
+        NoDelay,
+        config,
+    )
+    .await?;
+    Ok(())
+}
+
+/// SPI device forwarding writes to a [`CommandSink`], as commands or data by the DC level.
+struct RecordingSpi<'a, S> {
+    sink: &'a mut S,
+    dc_high: &'a Cell<bool>,
+}
+
+impl<S> RecordingSpi<'_, S> {
+    fn write(&mut self, data: &[u8])
+    where
+        S: CommandSink,
+    {
+        if self.dc_high.get() {
+            self.sink.data(data);
+        } else {
+            data.iter().for_each(|&command| self.sink.command(command));
+        }
+    }
+}
+
+impl<S> spi::ErrorType for RecordingSpi<'_, S> {
+    type Error = Infallible;
+}
+
+impl<S: CommandSink> SpiDevice for RecordingSpi<'_, S> {
+    async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), Infallible> {
+        for operation in operations {
+            match operation {
+                Operation::Write(data) => self.write(data),
+                Operation::Transfer(read, write) => {
+                    self.write(write);
+                    read.fill(0);
+                }
+                Operation::Read(read) | Operation::TransferInPlace(read) => read.fill(0),
+                Operation::DelayNs(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Data/command pin whose level decides how [`RecordingSpi`] records writes.
+struct DcPin<'a>(&'a Cell<bool>);
+
+impl digital::ErrorType for DcPin<'_> {
+    type Error = Infallible;
+}
+
+impl OutputPin for DcPin<'_> {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.0.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.0.set(true);
+        Ok(())
+    }
+}
+
+/// Stand-in for the reset and backlight pins, and a BUSY line that is always idle.
+struct IdlePin;
+
+impl digital::ErrorType for IdlePin {
+    type Error = Infallible;
+}
+
+impl OutputPin for IdlePin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+impl InputPin for IdlePin {
+    fn is_high(&mut self) -> Result<bool, Infallible> {
+        Ok(false)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Infallible> {
+        Ok(true)
+    }
+}
+
+impl Wait for IdlePin {
+    async fn wait_for_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+struct NoDelay;
+
+impl DelayNs for NoDelay {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
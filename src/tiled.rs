@@ -0,0 +1,135 @@
+use crate::driver::{ColorMode, DisplayDriver};
+use crate::graphics::{BitOrder, assert_buffer_len, pack_pixel};
+use core::convert::Infallible;
+use core::future::{Future, poll_fn};
+use core::pin::pin;
+use core::task::Poll;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+/// Draw target spanning two panels mounted side by side, each behind its own controller.
+///
+/// Pixels left of `D1::X` go to the left panel, the rest to the right one shifted by `D1::X`,
+/// so the seam needs no special handling as long as both panels share the same height. Both
+/// panels use their native orientation.
+pub struct TiledDisplay<D1: DisplayDriver, D2: DisplayDriver<Error = D1::Error>, const N: usize> {
+    left: D1,
+    right: D2,
+    left_buffer: [u8; N],
+    right_buffer: [u8; N],
+}
+
+impl<D1, D2, const N: usize> TiledDisplay<D1, D2, N>
+where
+    D1: DisplayDriver,
+    D2: DisplayDriver<Error = D1::Error>,
+{
+    pub fn new(left: D1, right: D2) -> Self {
+        const {
+            assert_buffer_len(ColorMode::BlackWhite, D1::X, D1::Y, N);
+            assert_buffer_len(ColorMode::BlackWhite, D2::X, D2::Y, N);
+        }
+        Self {
+            left,
+            right,
+            left_buffer: [0; N],
+            right_buffer: [0; N],
+        }
+    }
+
+    /// Refreshes both panels concurrently, so the two halves update together.
+    pub async fn flush(&mut self) -> Result<(), D1::Error> {
+        let (left, right) = join(
+            self.left.draw_frame(&self.left_buffer),
+            self.right.draw_frame(&self.right_buffer),
+        )
+        .await;
+        left.and(right)
+    }
+}
+
+impl<D1, D2, const N: usize> Dimensions for TiledDisplay<D1, D2, N>
+where
+    D1: DisplayDriver,
+    D2: DisplayDriver<Error = D1::Error>,
+{
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(
+            Point::new(0, 0),
+            Size::new((D1::X + D2::X) as u32, D1::Y as u32),
+        )
+    }
+}
+
+impl<D1, D2, const N: usize> DrawTarget for TiledDisplay<D1, D2, N>
+where
+    D1: DisplayDriver,
+    D2: DisplayDriver<Error = D1::Error>,
+{
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounding_box = self.bounding_box();
+
+        for Pixel(point, color) in pixels.into_iter() {
+            if !bounding_box.contains(point) {
+                continue;
+            }
+
+            let (x, y) = (point.x as usize, point.y as usize);
+            if x < D1::X {
+                pack_pixel(
+                    &mut self.left_buffer,
+                    BitOrder::MsbFirst,
+                    [x, y],
+                    D1::X,
+                    color,
+                );
+            } else {
+                let x = x - D1::X;
+                pack_pixel(
+                    &mut self.right_buffer,
+                    BitOrder::MsbFirst,
+                    [x, y],
+                    D2::X,
+                    color,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Polls both futures until each has completed.
+async fn join<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+    let (mut a, mut b) = (pin!(a), pin!(b));
+    let (mut a_output, mut b_output) = (None, None);
+
+    poll_fn(|cx| {
+        if a_output.is_none()
+            && let Poll::Ready(output) = a.as_mut().poll(cx)
+        {
+            a_output = Some(output);
+        }
+        if b_output.is_none()
+            && let Poll::Ready(output) = b.as_mut().poll(cx)
+        {
+            b_output = Some(output);
+        }
+
+        match (a_output.take(), b_output.take()) {
+            (Some(a), Some(b)) => Poll::Ready((a, b)),
+            (a, b) => {
+                (a_output, b_output) = (a, b);
+                Poll::Pending
+            }
+        }
+    })
+    .await
+}
@@ -0,0 +1,107 @@
+use crate::driver::{ColorMode, DisplayDriver};
+use crate::graphics::{
+    BitOrder, Rotation, assert_buffer_len, logical_size, pack_pixel, to_hardware,
+};
+use core::convert::Infallible;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriColor {
+    Black,
+    White,
+    Red,
+}
+
+impl PixelColor for TriColor {
+    type Raw = ();
+}
+
+/// Buffered draw target for black/white/red panels.
+///
+/// Both planes live in one buffer, the black/white plane followed by the red one, sized by the
+/// same [`frame_len`] formula as [`BufferedDisplay`] with [`ColorMode::TriColor`]. Code that
+/// passed the two planes as separate `X * Y / 8` byte arrays should pass their concatenation
+/// instead.
+///
+/// [`frame_len`]: crate::driver::frame_len
+/// [`BufferedDisplay`]: crate::graphics::BufferedDisplay
+pub struct TriColorDisplay<D: DisplayDriver, const N: usize> {
+    driver: D,
+    buffer: [u8; N],
+    rotation: Rotation,
+}
+
+impl<D: DisplayDriver, const N: usize> TriColorDisplay<D, N> {
+    pub fn new(driver: D, buffer: [u8; N], rotation: Rotation) -> Self {
+        const { assert_buffer_len(ColorMode::TriColor, D::X, D::Y, N) }
+        Self {
+            driver,
+            buffer,
+            rotation,
+        }
+    }
+
+    /// Creates a display backed by a zeroed buffer, i.e. all black.
+    pub fn with_driver(driver: D, rotation: Rotation) -> Self {
+        Self::new(driver, [0; N], rotation)
+    }
+
+    pub async fn flush(&mut self) -> Result<(), D::Error> {
+        let (black_white, red) = self.buffer.split_at(N / 2);
+        self.driver.draw_tri_color_frame(black_white, red).await
+    }
+
+    /// Writes only the red plane and runs a full refresh, for dynamic red content over a static
+    /// black/white image.
+    ///
+    /// A full refresh reads both RAM banks, but the controller keeps RAM contents across
+    /// refreshes, so the black/white bank still holds what the last [`Self::flush`] wrote. It
+    /// doesn't survive a reset or a wake from deep sleep, and partial refreshes on the same
+    /// driver overwrite the red bank; call [`Self::flush`] after any of those.
+    pub async fn flush_red_only(&mut self) -> Result<(), D::Error> {
+        let (black_white, red) = self.buffer.split_at(N / 2);
+        self.driver.draw_red_frame(black_white, red).await
+    }
+}
+
+impl<D: DisplayDriver, const N: usize> Dimensions for TriColorDisplay<D, N> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::new(0, 0), logical_size(self.rotation, D::X, D::Y))
+    }
+}
+
+impl<D: DisplayDriver, const N: usize> DrawTarget for TriColorDisplay<D, N> {
+    type Color = TriColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (black_white_plane, red_plane) = self.buffer.split_at_mut(N / 2);
+        for Pixel(point, color) in pixels.into_iter() {
+            let Some(hw) = to_hardware(self.rotation, point, D::X, D::Y) else {
+                continue;
+            };
+
+            let (black_white, red) = plane_colors(color);
+
+            pack_pixel(black_white_plane, BitOrder::MsbFirst, hw, D::X, black_white);
+            pack_pixel(red_plane, BitOrder::MsbFirst, hw, D::X, red);
+        }
+
+        Ok(())
+    }
+}
+
+/// Bits a tri-color pixel sets in the black/white and red planes. The red plane takes
+/// precedence, so red pixels leave the black/white plane white.
+pub(crate) fn plane_colors(color: TriColor) -> (BinaryColor, BinaryColor) {
+    match color {
+        TriColor::Black => (BinaryColor::Off, BinaryColor::Off),
+        TriColor::White => (BinaryColor::On, BinaryColor::Off),
+        TriColor::Red => (BinaryColor::On, BinaryColor::On),
+    }
+}
@@ -0,0 +1,151 @@
+use crate::driver::DisplayDriver;
+use crate::graphics::BufferedDisplay;
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+
+/// A fixed-position text value, such as a temperature or clock, that is redrawn on its own.
+///
+/// Each update blanks the field's rectangle before drawing, so old glyphs don't linger as
+/// overdraw, and partially refreshes only that rectangle. Text is clipped to the field.
+pub struct TextField<'a> {
+    area: Rectangle,
+    style: MonoTextStyle<'a, BinaryColor>,
+    background: BinaryColor,
+}
+
+impl<'a> TextField<'a> {
+    pub fn new(
+        area: Rectangle,
+        style: MonoTextStyle<'a, BinaryColor>,
+        background: BinaryColor,
+    ) -> Self {
+        Self {
+            area,
+            style,
+            background,
+        }
+    }
+
+    pub fn bounding_box(&self) -> Rectangle {
+        self.area
+    }
+
+    /// Replaces the field's text and refreshes just the field.
+    pub async fn update<D: DisplayDriver, const N: usize>(
+        &mut self,
+        display: &mut BufferedDisplay<D, N>,
+        text: &str,
+    ) -> Result<(), D::Error> {
+        self.draw(display, text);
+        display.flush_area(self.area).await
+    }
+
+    /// Renders the text into the buffer without refreshing, e.g. to batch several fields.
+    pub fn draw<D: DisplayDriver, const N: usize>(
+        &self,
+        display: &mut BufferedDisplay<D, N>,
+        text: &str,
+    ) {
+        let Ok(()) = display.fill_solid(&self.area, self.background);
+
+        let mut clipped = display.clipped(&self.area);
+        let Ok(_) = Text::with_baseline(text, self.area.top_left, self.style, Baseline::Top)
+            .draw(&mut clipped);
+    }
+}
+
+/// Horizontal bar filled from the left in proportion to a 0–100 value, e.g. progress or battery
+/// level.
+///
+/// Updates only redraw and refresh the strip between the old and the new fill level, so frequent
+/// small changes stay cheap.
+pub struct ProgressBar {
+    area: Rectangle,
+    fill: BinaryColor,
+    background: BinaryColor,
+    value: Option<u8>,
+}
+
+impl ProgressBar {
+    pub fn new(area: Rectangle, fill: BinaryColor, background: BinaryColor) -> Self {
+        Self {
+            area,
+            fill,
+            background,
+            value: None,
+        }
+    }
+
+    pub fn bounding_box(&self) -> Rectangle {
+        self.area
+    }
+
+    /// Last value shown, or `None` before the first update.
+    pub fn value(&self) -> Option<u8> {
+        self.value
+    }
+
+    /// Shows `value` (clamped to 100), refreshing just the part of the bar that changed. The
+    /// first update draws and refreshes the whole bar.
+    pub async fn update<D: DisplayDriver, const N: usize>(
+        &mut self,
+        display: &mut BufferedDisplay<D, N>,
+        value: u8,
+    ) -> Result<(), D::Error> {
+        if let Some(changed) = self.draw(display, value) {
+            display.flush_area(changed).await?;
+        }
+        Ok(())
+    }
+
+    /// Renders `value` (clamped to 100) into the buffer without refreshing, returning the area
+    /// that changed, if any.
+    pub fn draw<D: DisplayDriver, const N: usize>(
+        &mut self,
+        display: &mut BufferedDisplay<D, N>,
+        value: u8,
+    ) -> Option<Rectangle> {
+        let value = value.min(100);
+        let new_width = self.fill_width(value);
+
+        let changed = match self.value.map(|old| self.fill_width(old)) {
+            None => {
+                let filled = Rectangle::new(
+                    self.area.top_left,
+                    Size::new(new_width, self.area.size.height),
+                );
+                let Ok(()) = display.fill_solid(&self.area, self.background);
+                let Ok(()) = display.fill_solid(&filled, self.fill);
+                self.area
+            }
+            Some(old_width) if old_width == new_width => {
+                self.value = Some(value);
+                return None;
+            }
+            Some(old_width) => {
+                let (start, end) = (old_width.min(new_width), old_width.max(new_width));
+                let strip = Rectangle::new(
+                    self.area.top_left + Point::new(start as i32, 0),
+                    Size::new(end - start, self.area.size.height),
+                );
+                let color = if new_width > old_width {
+                    self.fill
+                } else {
+                    self.background
+                };
+                let Ok(()) = display.fill_solid(&strip, color);
+                strip
+            }
+        };
+
+        self.value = Some(value);
+        Some(changed)
+    }
+
+    fn fill_width(&self, value: u8) -> u32 {
+        self.area.size.width * value as u32 / 100
+    }
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::DisplayDriver;
+
+const SHOWN: [u8; 5000] = [0xa5; 5000];
+const NEXT: [u8; 5000] = [0x3c; 5000];
+
+fn config() -> Ssd1681Config<200, 200> {
+    Ssd1681Config {
+        auto_backlight_on_refresh: true,
+        keep_shown_frame_in_red_ram: true,
+        ..Ssd1681Config::default()
+    }
+}
+
+#[test]
+fn an_aborted_frame_is_followed_by_a_full_refresh_in_the_dark() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config());
+    display.retain_frames_in(vec![0; 5000].leak());
+    block_on(display.start_frame(&SHOWN)).unwrap();
+    // as left by a refresh whose future was dropped while the light was on
+    panel.borrow_mut().backlight = true;
+
+    block_on(display.abort_refresh()).unwrap();
+
+    assert!(!panel.borrow().backlight);
+    let state = panel.borrow();
+    assert!(state.black_white.iter().all(|&byte| byte == 0xa5));
+    assert!(state.red.iter().all(|&byte| byte == 0xa5));
+    drop(state);
+
+    panel.borrow_mut().commands.clear();
+    block_on(display.draw_frame(&NEXT)).unwrap();
+
+    let state = panel.borrow();
+    assert_eq!(state.full_refreshes(), [true]);
+    assert_eq!(state.sent(0x20).len(), 1);
+    assert!(!state.backlight);
+    assert!(state.black_white.iter().all(|&byte| byte == 0x3c));
+    // the abort restored the red bank, so only the frame itself is mirrored into it afterwards
+    assert_eq!(state.sent(0x26).len(), 1);
+    assert!(state.red.iter().all(|&byte| byte == 0x3c));
+}
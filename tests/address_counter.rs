@@ -0,0 +1,30 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::errors::DisplayError;
+
+#[test]
+fn the_last_column_and_line_are_accepted() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+
+    block_on(display.set_address_counter(24, 199)).unwrap();
+
+    let state = panel.borrow();
+    assert_eq!(state.sent(0x4e).last(), Some(&&[24][..]));
+    assert_eq!(state.sent(0x4f).last(), Some(&&[199, 0][..]));
+}
+
+#[test]
+fn one_past_the_last_column_or_line_is_out_of_bounds() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    panel.borrow_mut().commands.clear();
+
+    for (x, y) in [(25, 0), (0, 200)] {
+        let moved = block_on(display.set_address_counter(x, y));
+        assert!(matches!(moved, Err(DisplayError::OutOfBounds)), "{x}, {y}");
+    }
+    assert!(panel.borrow().commands.is_empty());
+}
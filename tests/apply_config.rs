@@ -0,0 +1,86 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use ssd1681_async::config::{ScanMode, Ssd1681Config};
+
+/// Initialises with `changed`, reverts to the default config and returns what `command` was
+/// last sent with.
+fn reverted(changed: Ssd1681Config<200, 200>, command: u8) -> Vec<u8> {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, changed);
+    panel.borrow_mut().commands.clear();
+
+    block_on(display.apply_config(Ssd1681Config::default())).unwrap();
+
+    let state = panel.borrow();
+    state.sent(command).last().expect("not resent").to_vec()
+}
+
+#[test]
+fn reverting_the_scan_mode_rewrites_driver_output_control() {
+    let changed = Ssd1681Config {
+        scan_mode: ScanMode::Interlaced,
+        ..Ssd1681Config::default()
+    };
+
+    assert_eq!(reverted(changed, 0x01), [199, 0, 0x00]);
+}
+
+#[test]
+fn reverting_the_gate_scan_start_rewrites_it() {
+    let changed = Ssd1681Config {
+        gate_scan_start: 8,
+        ..Ssd1681Config::default()
+    };
+
+    assert_eq!(reverted(changed, 0x0f), [0, 0]);
+}
+
+#[test]
+fn reverting_to_the_external_sensor_selects_it() {
+    // the internal sensor is the default, so revert from the external one and back
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    let external = Ssd1681Config {
+        use_internal_temp_sensor: false,
+        ..Ssd1681Config::default()
+    };
+
+    block_on(display.apply_config(external)).unwrap();
+    assert_eq!(panel.borrow().sent(0x18).last(), Some(&&[0x48][..]));
+    block_on(display.apply_config(Ssd1681Config::default())).unwrap();
+    assert_eq!(panel.borrow().sent(0x18).last(), Some(&&[0x80][..]));
+}
+
+#[test]
+fn reverting_the_border_waveform_writes_the_reset_value() {
+    let changed = Ssd1681Config {
+        border_waveform: Some(0x05),
+        ..Ssd1681Config::default()
+    };
+
+    assert_eq!(reverted(changed, 0x3c), [0xc0]);
+}
+
+#[test]
+fn reverting_the_voltages_writes_the_reset_values() {
+    let changed = || Ssd1681Config {
+        gate_voltage: Some(0x17),
+        source_voltage: Some([0x41, 0x00, 0x32]),
+        ..Ssd1681Config::default()
+    };
+
+    assert_eq!(reverted(changed(), 0x03), [0x00]);
+    assert_eq!(reverted(changed(), 0x04), [0x41, 0xa8, 0x32]);
+}
+
+#[test]
+fn an_unchanged_register_config_writes_nothing() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    panel.borrow_mut().commands.clear();
+
+    block_on(display.apply_config(Ssd1681Config::fast())).unwrap();
+
+    assert!(panel.borrow().commands.is_empty());
+}
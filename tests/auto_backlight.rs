@@ -0,0 +1,39 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::DisplayDriver;
+use ssd1681_async::errors::DisplayError;
+
+fn config() -> Ssd1681Config<200, 200> {
+    Ssd1681Config {
+        auto_backlight_on_refresh: true,
+        ..Ssd1681Config::default()
+    }
+}
+
+#[test]
+fn the_backlight_is_off_after_a_refresh() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config());
+
+    block_on(display.draw_frame(&[0xff; 5000])).unwrap();
+
+    assert!(!panel.borrow().backlight);
+}
+
+#[test]
+fn a_failed_refresh_switches_the_backlight_off_and_reports_its_error() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config());
+    {
+        let mut state = panel.borrow_mut();
+        let activations = state.sent(0x20).len();
+        state.fail_command = Some((0x20, activations));
+    }
+
+    let drawn = block_on(display.draw_frame(&[0xff; 5000]));
+
+    assert!(matches!(drawn, Err(DisplayError::SpiFailure)));
+    assert!(!panel.borrow().backlight);
+}
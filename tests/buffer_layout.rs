@@ -0,0 +1,92 @@
+mod common;
+
+use common::{PanelDriver, SSD1681_RAM, block_on, driver, panel};
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::graphics::{BufferLayout, BufferedDisplay, Rotation};
+
+const N: usize = 200 * 200 / 8;
+
+fn display(layout: BufferLayout) -> (common::Panel, BufferedDisplay<PanelDriver<200, 200>, N>) {
+    let panel = panel(SSD1681_RAM);
+    let driver = driver(&panel, Ssd1681Config::default());
+    let mut display = BufferedDisplay::<_, N>::with_driver(driver, Rotation::_0);
+    display.set_layout(layout);
+    panel.borrow_mut().commands.clear();
+    (panel, display)
+}
+
+fn draw(display: &mut BufferedDisplay<PanelDriver<200, 200>, N>, points: &[Point]) {
+    for &point in points {
+        Pixel(point, BinaryColor::On).draw(display).unwrap();
+    }
+}
+
+#[test]
+fn reversed_buffers_are_mirrored_into_ram() {
+    let (panel, mut display) = display(BufferLayout::Reversed);
+    draw(&mut display, &[Point::new(0, 0)]);
+
+    // the leftmost pixel is the last bit of the scanline in the buffer...
+    assert_eq!(display.buffer()[24], 0x01);
+    block_on(display.flush()).unwrap();
+
+    // ...and the first one in RAM, with the buffer left as it was
+    let state = panel.borrow();
+    assert_eq!(state.ram(false, 0, 0), 0x80);
+    assert_eq!(
+        state.black_white.iter().filter(|&&byte| byte != 0).count(),
+        1
+    );
+    assert_eq!(display.buffer()[24], 0x01);
+}
+
+#[test]
+fn reversed_partial_updates_write_the_mirrored_window() {
+    let (panel, mut display) = display(BufferLayout::Reversed);
+    draw(&mut display, &[Point::new(3, 10), Point::new(12, 11)]);
+    block_on(display.flush_dirty()).unwrap();
+
+    let state = panel.borrow();
+    // the window covers byte columns 0 and 1 of lines 10 and 11
+    assert_eq!(state.sent(0x44)[0], [0, 1]);
+    assert_eq!(state.sent(0x24)[0], [0x10, 0x00, 0x00, 0x08]);
+    assert_eq!(state.ram(false, 0, 10), 0x10);
+    assert_eq!(state.ram(false, 1, 11), 0x08);
+}
+
+#[test]
+fn both_layouts_put_the_same_bytes_in_ram() {
+    let points = [
+        Point::new(0, 0),
+        Point::new(7, 3),
+        Point::new(8, 3),
+        Point::new(105, 60),
+        Point::new(199, 199),
+    ];
+    let ram = |layout| {
+        let (panel, mut display) = display(layout);
+        draw(&mut display, &points);
+        block_on(display.flush()).unwrap();
+        let state = panel.borrow();
+        state.black_white.clone()
+    };
+
+    assert_eq!(ram(BufferLayout::Reversed), ram(BufferLayout::StandardMsb));
+}
+
+#[test]
+fn raw_pixels_are_panel_coordinates_in_both_layouts() {
+    let ram = |layout| {
+        let (panel, mut display) = display(layout);
+        assert!(display.set_pixel_raw(3, 5, true));
+        assert!(!display.set_pixel_raw(200, 5, true));
+        block_on(display.flush_dirty()).unwrap();
+        let state = panel.borrow();
+        (state.sent(0x44)[0].to_vec(), state.ram(false, 0, 5))
+    };
+
+    assert_eq!(ram(BufferLayout::Reversed), (vec![0, 0], 0x10));
+    assert_eq!(ram(BufferLayout::StandardMsb), (vec![0, 0], 0x10));
+}
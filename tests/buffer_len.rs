@@ -0,0 +1,14 @@
+use ssd1681_async::driver::buffer_len;
+
+#[test]
+fn packs_one_bit_per_pixel() {
+    assert_eq!(buffer_len(200, 200), 5000);
+    assert_eq!(buffer_len(16, 8), 16);
+}
+
+#[test]
+fn usable_in_const_context() {
+    const LEN: usize = buffer_len(128, 296);
+    let buffer = [0u8; LEN];
+    assert_eq!(buffer.len(), 4736);
+}
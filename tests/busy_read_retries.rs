@@ -0,0 +1,64 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::errors::DisplayError;
+
+fn config() -> Ssd1681Config<200, 200> {
+    Ssd1681Config {
+        busy_read_retries: 3,
+        busy_timeout_ms: Some(50),
+        ..Ssd1681Config::default()
+    }
+}
+
+/// Runs a refresh after failing the first `errors` BUSY reads, returning its result and how
+/// many times BUSY was awaited.
+fn refresh_with_flaky_busy(errors: u8) -> (Result<(), DisplayError>, usize) {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config());
+    {
+        let mut state = panel.borrow_mut();
+        state.busy_waits = 0;
+        state.busy_wait_errors = errors;
+    }
+
+    let refreshed = block_on(display.refresh());
+    (refreshed, panel.borrow().busy_waits)
+}
+
+#[test]
+fn failed_busy_reads_are_retried() {
+    let (refreshed, clean_waits) = refresh_with_flaky_busy(0);
+    refreshed.unwrap();
+
+    for errors in 1..=3 {
+        let (refreshed, waits) = refresh_with_flaky_busy(errors);
+        refreshed.unwrap();
+        assert_eq!(waits, clean_waits + usize::from(errors));
+    }
+}
+
+#[test]
+fn a_read_failing_past_the_retries_is_reported() {
+    let (refreshed, waits) = refresh_with_flaky_busy(4);
+
+    assert!(matches!(refreshed, Err(DisplayError::BusyPinFailure)));
+    assert_eq!(waits, 4);
+}
+
+#[test]
+fn a_timeout_is_not_retried() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config());
+    {
+        let mut state = panel.borrow_mut();
+        state.busy_waits = 0;
+        state.busy_wait_stuck = true;
+    }
+
+    let refreshed = block_on(display.refresh());
+
+    assert!(matches!(refreshed, Err(DisplayError::BusyTimeout)));
+    assert_eq!(panel.borrow().busy_waits, 1);
+}
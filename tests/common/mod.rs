@@ -0,0 +1,440 @@
+#![allow(dead_code)]
+
+use core::cell::RefCell;
+use core::convert::Infallible;
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+use embedded_graphics::primitives::Rectangle;
+use embedded_hal::digital::{self, InputPin, OutputPin};
+use embedded_hal::spi::{self, Operation};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::spi::SpiDevice;
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::{DisplayDriver, Ssd1681};
+use ssd1681_async::errors::DisplayError;
+use std::rc::Rc;
+
+/// Polls `future` to completion; nothing in the tests ever really waits.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// Driver for tests that only render into the buffer.
+pub struct NullDriver<const X: usize, const Y: usize>;
+
+impl<const X: usize, const Y: usize> DisplayDriver for NullDriver<X, Y> {
+    type Error = ();
+
+    const X: usize = X;
+    const Y: usize = Y;
+
+    async fn draw_frame(&mut self, _buffer: &[u8]) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn draw_partial(&mut self, _buffer: &[u8], _area: Rectangle) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn draw_tri_color_frame(&mut self, _black_white: &[u8], _red: &[u8]) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn enable_backlight(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn disable_backlight(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+/// Byte columns and gate lines of SSD1681 RAM, 200x200 pixels.
+pub const SSD1681_RAM: (usize, usize) = (25, 200);
+/// Byte columns and gate lines of SSD1680 RAM, 176x296 pixels.
+pub const SSD1680_RAM: (usize, usize) = (22, 296);
+
+/// A simulated controller: what was sent to it and what its RAM holds.
+///
+/// Commands are logged with their data; the RAM window (`0x44`/`0x45`), address counter
+/// (`0x4E`/`0x4F`), RAM writes (`0x24`/`0x26`) and reads (`0x41`/`0x27`) act on the two banks in
+/// data entry mode `0x03`. Writes outside the RAM are dropped and reads outside it return 0.
+/// Reads can be corrupted on demand through `corrupt_reads`.
+/// Other reads return zeros, as do all reads while `readable` is unset.
+pub struct PanelState {
+    pub commands: Vec<(u8, Vec<u8>)>,
+    pub black_white: Vec<u8>,
+    pub red: Vec<u8>,
+    /// Every `delay_ms` the driver awaited, in order.
+    pub delays_ms: Vec<u32>,
+    pub backlight: bool,
+    pub readable: bool,
+    /// BUSY reads high this many more times; `u32::MAX` keeps it high for good.
+    pub busy_high_reads: u32,
+    /// Awaits of a BUSY edge or level so far.
+    pub busy_waits: usize,
+    /// Fails this many more awaits of BUSY with a pin error.
+    pub busy_wait_errors: u8,
+    /// Awaits of BUSY never complete.
+    pub busy_wait_stuck: bool,
+    /// `(command, n)` fails the SPI transaction sending the `n`th `command` byte (counting from
+    /// 0 in `commands`), after logging it.
+    pub fail_command: Option<(u8, usize)>,
+    /// Inverts every byte read back by this many more `0x27` reads.
+    pub corrupt_reads: u8,
+    columns: usize,
+    rows: usize,
+    dc_high: bool,
+    x_window: (usize, usize),
+    y_window: (usize, usize),
+    x: usize,
+    y: usize,
+    read_red: bool,
+    dummy_pending: bool,
+    corrupting: bool,
+}
+
+pub type Panel = Rc<RefCell<PanelState>>;
+
+pub fn panel((columns, rows): (usize, usize)) -> Panel {
+    Rc::new(RefCell::new(PanelState {
+        commands: Vec::new(),
+        black_white: vec![0; columns * rows],
+        red: vec![0; columns * rows],
+        delays_ms: Vec::new(),
+        backlight: false,
+        readable: true,
+        busy_high_reads: 0,
+        busy_waits: 0,
+        busy_wait_errors: 0,
+        busy_wait_stuck: false,
+        fail_command: None,
+        corrupt_reads: 0,
+        columns,
+        rows,
+        dc_high: false,
+        x_window: (0, columns - 1),
+        y_window: (0, rows - 1),
+        x: 0,
+        y: 0,
+        read_red: false,
+        dummy_pending: false,
+        corrupting: false,
+    }))
+}
+
+impl PanelState {
+    /// The byte at column `x` of line `y` in the black/white or red bank.
+    pub fn ram(&self, red: bool, x: usize, y: usize) -> u8 {
+        let bank = if red { &self.red } else { &self.black_white };
+        bank[y * self.columns + x]
+    }
+
+    /// Whether `command` was sent, with the data that followed it each time.
+    pub fn sent(&self, command: u8) -> Vec<&[u8]> {
+        self.commands
+            .iter()
+            .filter(|(sent, _)| *sent == command)
+            .map(|(_, data)| data.as_slice())
+            .collect()
+    }
+
+    /// Whether each display update control write ran a full refresh rather than a partial one.
+    pub fn full_refreshes(&self) -> Vec<bool> {
+        self.sent(0x22)
+            .iter()
+            .map(|data| data[0] & 0x08 == 0)
+            .collect()
+    }
+
+    fn command(&mut self, command: u8) {
+        if command == 0x27 {
+            self.dummy_pending = true;
+            self.corrupting = self.corrupt_reads > 0;
+            self.corrupt_reads = self.corrupt_reads.saturating_sub(1);
+        }
+        self.commands.push((command, Vec::new()));
+    }
+
+    fn data(&mut self, byte: u8) {
+        let Some((command, data)) = self.commands.last_mut() else {
+            return;
+        };
+        data.push(byte);
+        let word = |low: usize| usize::from(data[low]) | usize::from(data[low + 1]) << 8;
+        match (*command, data.len()) {
+            (0x44, 2) => self.x_window = (data[0].into(), data[1].into()),
+            (0x45, 4) => self.y_window = (word(0), word(2)),
+            (0x4e, 1) => self.x = data[0].into(),
+            (0x4f, 2) => self.y = word(0),
+            (0x41, 1) => self.read_red = data[0] & 0x01 != 0,
+            (0x24 | 0x26, _) => {
+                let red = *command == 0x26;
+                if let Some(index) = self.index() {
+                    let bank = if red {
+                        &mut self.red
+                    } else {
+                        &mut self.black_white
+                    };
+                    bank[index] = byte;
+                }
+                self.advance();
+            }
+            _ => {}
+        }
+    }
+
+    fn read(&mut self) -> u8 {
+        if !self.readable || !matches!(self.commands.last(), Some((0x27, _))) {
+            return 0;
+        }
+        if self.dummy_pending {
+            self.dummy_pending = false;
+            return 0;
+        }
+        let byte = self.index().map_or(0, |index| {
+            if self.read_red {
+                self.red[index]
+            } else {
+                self.black_white[index]
+            }
+        });
+        self.advance();
+        if self.corrupting { !byte } else { byte }
+    }
+
+    fn index(&self) -> Option<usize> {
+        (self.x < self.columns && self.y < self.rows).then(|| self.y * self.columns + self.x)
+    }
+
+    fn advance(&mut self) {
+        if self.x != self.x_window.1 {
+            self.x += 1;
+            return;
+        }
+        self.x = self.x_window.0;
+        self.y = if self.y == self.y_window.1 {
+            self.y_window.0
+        } else {
+            self.y + 1
+        };
+    }
+}
+
+pub struct PanelSpi(pub Panel);
+
+impl PanelSpi {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), spi::ErrorKind> {
+        let mut state = self.0.borrow_mut();
+        for &byte in bytes {
+            if state.dc_high {
+                state.data(byte);
+                continue;
+            }
+            let sent = state.sent(byte).len();
+            state.command(byte);
+            if state.fail_command == Some((byte, sent)) {
+                return Err(spi::ErrorKind::Other);
+            }
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, buffer: &mut [u8]) {
+        let mut state = self.0.borrow_mut();
+        buffer.iter_mut().for_each(|byte| *byte = state.read());
+    }
+}
+
+impl spi::ErrorType for PanelSpi {
+    type Error = spi::ErrorKind;
+}
+
+impl SpiDevice for PanelSpi {
+    async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), spi::ErrorKind> {
+        for operation in operations {
+            match operation {
+                Operation::Write(data) => self.write(data)?,
+                Operation::Transfer(read, write) => {
+                    self.write(write)?;
+                    self.read(read);
+                }
+                Operation::Read(read) | Operation::TransferInPlace(read) => self.read(read),
+                Operation::DelayNs(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The panel's DC line.
+pub struct PanelDc(pub Panel);
+
+impl digital::ErrorType for PanelDc {
+    type Error = Infallible;
+}
+
+impl OutputPin for PanelDc {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.0.borrow_mut().dc_high = false;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.0.borrow_mut().dc_high = true;
+        Ok(())
+    }
+}
+
+/// The panel's backlight switch.
+pub struct PanelBacklight(pub Panel);
+
+impl digital::ErrorType for PanelBacklight {
+    type Error = Infallible;
+}
+
+impl OutputPin for PanelBacklight {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.0.borrow_mut().backlight = false;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.0.borrow_mut().backlight = true;
+        Ok(())
+    }
+}
+
+/// The reset pin.
+pub struct IdlePin;
+
+impl digital::ErrorType for IdlePin {
+    type Error = Infallible;
+}
+
+impl OutputPin for IdlePin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// The panel's BUSY line: idle unless `busy_high_reads` or the `busy_wait_*` fields say
+/// otherwise.
+pub struct PanelBusy(pub Panel);
+
+impl PanelBusy {
+    fn wait(&mut self) -> impl Future<Output = Result<(), digital::ErrorKind>> + use<> {
+        let mut state = self.0.borrow_mut();
+        state.busy_waits += 1;
+        let result = if state.busy_wait_errors > 0 {
+            state.busy_wait_errors -= 1;
+            Some(Err(digital::ErrorKind::Other))
+        } else if state.busy_wait_stuck {
+            None
+        } else {
+            Some(Ok(()))
+        };
+        async move {
+            match result {
+                Some(result) => result,
+                None => core::future::pending().await,
+            }
+        }
+    }
+}
+
+impl digital::ErrorType for PanelBusy {
+    type Error = digital::ErrorKind;
+}
+
+impl InputPin for PanelBusy {
+    fn is_high(&mut self) -> Result<bool, digital::ErrorKind> {
+        let mut state = self.0.borrow_mut();
+        if state.busy_high_reads == 0 {
+            return Ok(false);
+        }
+        if state.busy_high_reads != u32::MAX {
+            state.busy_high_reads -= 1;
+        }
+        Ok(true)
+    }
+
+    fn is_low(&mut self) -> Result<bool, digital::ErrorKind> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+impl Wait for PanelBusy {
+    async fn wait_for_high(&mut self) -> Result<(), digital::ErrorKind> {
+        self.wait().await
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), digital::ErrorKind> {
+        self.wait().await
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), digital::ErrorKind> {
+        self.wait().await
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), digital::ErrorKind> {
+        self.wait().await
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), digital::ErrorKind> {
+        self.wait().await
+    }
+}
+
+/// Delay that returns at once and logs the milliseconds asked for.
+pub struct PanelDelay(pub Panel);
+
+impl DelayNs for PanelDelay {
+    async fn delay_ns(&mut self, _ns: u32) {}
+
+    async fn delay_ms(&mut self, ms: u32) {
+        self.0.borrow_mut().delays_ms.push(ms);
+    }
+}
+
+pub type PanelDriver<const X: usize, const Y: usize> =
+    Ssd1681<X, Y, PanelSpi, PanelBusy, PanelDc, PanelBacklight, IdlePin, PanelDelay>;
+
+/// Initialises a driver talking to `panel`, with its backlight pin wired up.
+pub fn driver<const X: usize, const Y: usize>(
+    panel: &Panel,
+    config: Ssd1681Config<X, Y>,
+) -> PanelDriver<X, Y> {
+    try_driver(panel, config).unwrap()
+}
+
+/// Like [`driver`], returning the error if init fails.
+pub fn try_driver<const X: usize, const Y: usize>(
+    panel: &Panel,
+    config: Ssd1681Config<X, Y>,
+) -> Result<PanelDriver<X, Y>, DisplayError> {
+    block_on(Ssd1681::new(
+        PanelSpi(panel.clone()),
+        PanelBusy(panel.clone()),
+        PanelDc(panel.clone()),
+        IdlePin,
+        Some(PanelBacklight(panel.clone())),
+        PanelDelay(panel.clone()),
+        config,
+    ))
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::NullDriver;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use ssd1681_async::graphics::{Blend, Layer};
+use ssd1681_async::graphics::{BufferedDisplay, Rotation};
+
+const X: usize = 16;
+const Y: usize = 8;
+const N: usize = X * Y / 8;
+
+fn lit(display: &BufferedDisplay<NullDriver<X, Y>, N>) -> Vec<(i32, i32)> {
+    let mut lit = Vec::new();
+    for y in 0..Y as i32 {
+        for x in 0..X as i32 {
+            if display.pixel(Point::new(x, y)) == Some(BinaryColor::On) {
+                lit.push((x, y));
+            }
+        }
+    }
+    lit
+}
+
+// 3x2 layer: top row fully set, bottom row only its first pixel
+const DATA: [u8; 2] = [0b1110_0000, 0b1000_0000];
+
+fn layer(position: Point, rotation: Rotation, blend: Blend) -> Layer<'static> {
+    Layer {
+        data: &DATA,
+        size: Size::new(3, 2),
+        position,
+        rotation,
+        blend,
+    }
+}
+
+#[test]
+fn layers_are_placed_at_their_position() {
+    let mut display = BufferedDisplay::<_, N>::with_driver(NullDriver::<X, Y>, Rotation::_0);
+    display.compose(&[layer(Point::new(4, 1), Rotation::_0, Blend::Overwrite)]);
+
+    assert_eq!(lit(&display), vec![(4, 1), (5, 1), (6, 1), (4, 2)]);
+}
+
+#[test]
+fn layers_rotate_within_their_bounds() {
+    let mut display = BufferedDisplay::<_, N>::with_driver(NullDriver::<X, Y>, Rotation::_0);
+    display.compose(&[layer(Point::zero(), Rotation::_180, Blend::Overwrite)]);
+
+    assert_eq!(lit(&display), vec![(2, 0), (0, 1), (1, 1), (2, 1)]);
+}
+
+#[test]
+fn overwrite_clears_and_or_keeps_what_is_below() {
+    let mut display = BufferedDisplay::<_, N>::with_driver(NullDriver::<X, Y>, Rotation::_0);
+    Pixel(Point::new(1, 1), BinaryColor::On)
+        .draw(&mut display)
+        .unwrap();
+
+    display.compose(&[layer(Point::zero(), Rotation::_0, Blend::Or)]);
+    assert!(lit(&display).contains(&(1, 1)));
+
+    display.compose(&[layer(Point::zero(), Rotation::_0, Blend::Overwrite)]);
+    assert!(!lit(&display).contains(&(1, 1)));
+}
+
+#[test]
+fn scaled_bitmap_matches_scaled_pixels() {
+    // 3x2 bitmap scaled 3x, partly off the right edge
+    let (size, scale, top_left) = (Size::new(3, 2), 3, Point::new(1, 1));
+
+    for rotation in [Rotation::_0, Rotation::_90, Rotation::_180, Rotation::_270] {
+        let mut expected = BufferedDisplay::<_, N>::with_driver(NullDriver::<X, Y>, rotation);
+        expected.clear(BinaryColor::On).unwrap();
+        for y in 0..size.height as i32 {
+            for x in 0..size.width as i32 {
+                let on = DATA[y as usize] & (0b1000_0000 >> x) != 0;
+                let square = Rectangle::new(top_left + Point::new(x, y) * scale, Size::new(3, 3));
+                for point in square.points() {
+                    Pixel(point, BinaryColor::from(on))
+                        .draw(&mut expected)
+                        .unwrap();
+                }
+            }
+        }
+
+        let mut display = BufferedDisplay::<_, N>::with_driver(NullDriver::<X, Y>, rotation);
+        display.clear(BinaryColor::On).unwrap();
+        display.draw_scaled_bitmap(&DATA, size, scale as u32, top_left);
+
+        assert_eq!(display.buffer(), expected.buffer());
+    }
+}
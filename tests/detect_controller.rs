@@ -0,0 +1,38 @@
+mod common;
+
+use common::{SSD1680_RAM, SSD1681_RAM, block_on, driver, panel};
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::Controller;
+
+fn detect(ram: (usize, usize), readable: bool) -> Controller {
+    let panel = panel(ram);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    panel.borrow_mut().readable = readable;
+    block_on(display.detect_controller()).unwrap()
+}
+
+#[test]
+fn ssd1681_reads_back_beyond_176_columns() {
+    assert_eq!(detect(SSD1681_RAM, true), Controller::Ssd1681);
+}
+
+#[test]
+fn ssd1680_reads_back_only_within_176_columns() {
+    assert_eq!(detect(SSD1680_RAM, true), Controller::Ssd1680);
+}
+
+#[test]
+fn a_bus_that_cant_read_is_unknown() {
+    assert_eq!(detect(SSD1681_RAM, false), Controller::Unknown);
+}
+
+#[test]
+fn the_full_ram_window_is_restored() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    block_on(display.detect_controller()).unwrap();
+
+    let state = panel.borrow();
+    assert_eq!(state.sent(0x44).last(), Some(&&[0, 24][..]));
+    assert_eq!(state.sent(0x45).last(), Some(&&[0, 0, 199, 0][..]));
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::NullDriver;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray8};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Circle, PrimitiveStyle};
+use ssd1681_async::graphics::{BufferedDisplay, Rotation};
+
+const X: usize = 32;
+const Y: usize = 32;
+const N: usize = X * Y / 8;
+
+fn lit(display: &BufferedDisplay<NullDriver<X, Y>, N>) -> Vec<Point> {
+    display
+        .bounding_box()
+        .points()
+        .filter(|&point| display.pixel(point) == Some(BinaryColor::On))
+        .collect()
+}
+
+#[test]
+fn thick_gray_stroke_dithers_within_the_stroke() {
+    let circle = Circle::new(Point::new(4, 4), 24);
+
+    let mut ring = BufferedDisplay::<_, N>::with_driver(NullDriver::<X, Y>, Rotation::_0);
+    circle
+        .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 5))
+        .draw(&mut ring)
+        .unwrap();
+    let ring = lit(&ring);
+
+    let mut display = BufferedDisplay::<_, N>::with_driver(NullDriver::<X, Y>, Rotation::_0);
+    circle
+        .into_styled(PrimitiveStyle::with_stroke(Gray8::new(128), 5))
+        .draw(&mut display.dithered())
+        .unwrap();
+    let gray = lit(&display);
+
+    // mid gray sets half the pixels of the stroke and nothing outside it
+    assert!(gray.iter().all(|point| ring.contains(point)));
+    let percent = gray.len() * 100 / ring.len();
+    assert!((40..=60).contains(&percent), "{percent}% set");
+
+    circle
+        .into_styled(PrimitiveStyle::with_stroke(Gray8::WHITE, 5))
+        .draw(&mut display.dithered())
+        .unwrap();
+    assert_eq!(lit(&display), ring);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::NullDriver;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use ssd1681_async::graphics::{BufferLayout, BufferedDisplay, Rotation};
+
+const X: usize = 200;
+const Y: usize = 104;
+const N: usize = X * Y / 8;
+
+type Display = BufferedDisplay<NullDriver<X, Y>, N>;
+
+fn display(rotation: Rotation, layout: BufferLayout) -> Display {
+    let mut display = BufferedDisplay::<_, N>::with_driver(NullDriver::<X, Y>, rotation);
+    display.set_layout(layout);
+    // a pseudo-random background, so filled bytes and bits must both land exactly
+    let mut state = 0x2545_f491u32;
+    display.fill_with(|_, _| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        BinaryColor::from(state & 1 != 0)
+    });
+    display
+}
+
+#[test]
+fn fill_solid_matches_drawing_pixel_by_pixel() {
+    let rotations = [Rotation::_0, Rotation::_90, Rotation::_180, Rotation::_270];
+    let layouts = [BufferLayout::StandardMsb, BufferLayout::Reversed];
+    // unaligned edges, whole bytes in between, and one partly off screen
+    let areas = [
+        Rectangle::new(Point::new(3, 5), Size::new(37, 20)),
+        Rectangle::new(Point::new(0, 0), Size::new(8, 8)),
+        Rectangle::new(Point::new(90, 60), Size::new(200, 200)),
+    ];
+
+    for (r, rotation) in rotations.into_iter().enumerate() {
+        for (l, layout) in layouts.into_iter().enumerate() {
+            for area in areas {
+                for color in [BinaryColor::On, BinaryColor::Off] {
+                    let mut filled = display(rotation, layout);
+                    filled.fill_solid(&area, color).unwrap();
+
+                    let mut drawn = display(rotation, layout);
+                    drawn
+                        .draw_iter(area.points().map(|point| Pixel(point, color)))
+                        .unwrap();
+
+                    assert!(
+                        filled.buffer() == drawn.buffer(),
+                        "rotation {r}, layout {l}, {area:?}, {color:?}"
+                    );
+                }
+            }
+        }
+    }
+}
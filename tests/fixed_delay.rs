@@ -0,0 +1,52 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use ssd1681_async::config::{BusyStrategy, Ssd1681Config};
+use ssd1681_async::driver::DisplayDriver;
+
+const WAIT_MS: u32 = 2000;
+
+fn config() -> Ssd1681Config<200, 200> {
+    Ssd1681Config {
+        busy_strategy: BusyStrategy::FixedDelay(WAIT_MS),
+        ..Ssd1681Config::default()
+    }
+}
+
+fn fixed_waits(panel: &common::Panel) -> usize {
+    let state = panel.borrow();
+    state.delays_ms.iter().filter(|&&ms| ms == WAIT_MS).count()
+}
+
+#[test]
+fn init_waits_only_for_the_reset_and_the_clearing_refresh() {
+    let panel = panel(SSD1681_RAM);
+    driver(&panel, config());
+
+    assert_eq!(fixed_waits(&panel), 2);
+}
+
+#[test]
+fn init_without_clearing_waits_only_for_the_reset() {
+    let panel = panel(SSD1681_RAM);
+    driver(
+        &panel,
+        Ssd1681Config {
+            clear_on_init: false,
+            ..config()
+        },
+    );
+
+    assert_eq!(fixed_waits(&panel), 1);
+}
+
+#[test]
+fn a_frame_waits_once_for_its_refresh() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config());
+    panel.borrow_mut().delays_ms.clear();
+
+    block_on(display.draw_frame(&[0xff; 5000])).unwrap();
+
+    assert_eq!(fixed_waits(&panel), 1);
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::{PanelDriver, SSD1681_RAM, block_on, driver, panel};
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::graphics::{BufferLayout, BufferedDisplay, Rotation};
+
+const N: usize = 200 * 200 / 8;
+
+fn display(layout: BufferLayout) -> (common::Panel, BufferedDisplay<PanelDriver<200, 200>, N>) {
+    let panel = panel(SSD1681_RAM);
+    let driver = driver(&panel, Ssd1681Config::default());
+    let mut display = BufferedDisplay::<_, N>::with_driver(driver, Rotation::_0);
+    display.set_layout(layout);
+    panel.borrow_mut().commands.clear();
+    (panel, display)
+}
+
+#[test]
+fn an_empty_buffer_isnt_refreshed() {
+    let (panel, mut display) = display(BufferLayout::StandardMsb);
+
+    block_on(display.flush_trimmed(BinaryColor::Off)).unwrap();
+
+    assert!(panel.borrow().commands.is_empty());
+}
+
+#[test]
+fn a_single_corner_byte_is_the_whole_window() {
+    let (panel, mut display) = display(BufferLayout::StandardMsb);
+    Pixel(Point::new(199, 199), BinaryColor::On)
+        .draw(&mut display)
+        .unwrap();
+
+    block_on(display.flush_trimmed(BinaryColor::Off)).unwrap();
+
+    let state = panel.borrow();
+    assert_eq!(state.sent(0x44)[0], &[24, 24][..]);
+    assert_eq!(state.sent(0x45)[0], &[199, 0, 199, 0][..]);
+    assert_eq!(state.sent(0x24)[0], &[0x01][..]);
+    assert_eq!(state.ram(false, 24, 199), 0x01);
+}
+
+#[test]
+fn reversed_buffers_trim_to_the_mirrored_window() {
+    // the last logical column is the first byte of a reversed scanline, and the last RAM byte
+    for (point, buffer_column, ram_column, ram_byte) in [
+        (Point::new(199, 199), 0, 24, 0x01),
+        (Point::new(0, 199), 24, 0, 0x80),
+    ] {
+        let (panel, mut display) = display(BufferLayout::Reversed);
+        Pixel(point, BinaryColor::On).draw(&mut display).unwrap();
+
+        block_on(display.flush_trimmed(BinaryColor::Off)).unwrap();
+
+        let state = panel.borrow();
+        assert_eq!(state.sent(0x44)[0], &[ram_column, ram_column][..]);
+        assert_eq!(state.sent(0x45)[0], &[199, 0, 199, 0][..]);
+        assert_eq!(state.ram(false, ram_column.into(), 199), ram_byte);
+        assert_ne!(display.buffer()[199 * 25 + buffer_column], 0x00);
+    }
+}
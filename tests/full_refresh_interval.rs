@@ -0,0 +1,77 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::DisplayDriver;
+
+const FRAME: [u8; 5000] = [0xff; 5000];
+
+fn config(full_refresh_interval: u16) -> Ssd1681Config<200, 200> {
+    Ssd1681Config {
+        full_refresh_interval,
+        ..Ssd1681Config::default()
+    }
+}
+
+#[test]
+fn every_third_partial_update_is_promoted() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config(3));
+    panel.borrow_mut().commands.clear();
+
+    let area = Rectangle::new(Point::new(8, 8), Size::new(16, 16));
+    for _ in 0..9 {
+        block_on(display.draw_partial(&FRAME, area)).unwrap();
+    }
+
+    let refreshes = panel.borrow().full_refreshes();
+    assert_eq!(refreshes, [false, false, true].repeat(3));
+    assert_eq!(refreshes.iter().filter(|&&full| !full).count(), 6);
+}
+
+#[test]
+fn a_full_frame_restarts_the_count() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config(3));
+    panel.borrow_mut().commands.clear();
+
+    let area = Rectangle::new(Point::zero(), Size::new(8, 8));
+    block_on(display.draw_partial(&FRAME, area)).unwrap();
+    block_on(display.draw_partial(&FRAME, area)).unwrap();
+    block_on(display.draw_frame(&FRAME)).unwrap();
+    block_on(display.draw_partial(&FRAME, area)).unwrap();
+    block_on(display.draw_partial(&FRAME, area)).unwrap();
+
+    assert_eq!(
+        panel.borrow().full_refreshes(),
+        [false, false, true, false, false]
+    );
+}
+
+#[test]
+fn updates_of_nothing_visible_dont_count() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config(2));
+    panel.borrow_mut().commands.clear();
+
+    let off_screen = Rectangle::new(Point::new(300, 0), Size::new(8, 8));
+    for _ in 0..4 {
+        block_on(display.draw_partial(&FRAME, off_screen)).unwrap();
+    }
+    assert!(!panel.borrow().full_refreshes().contains(&true));
+}
+
+#[test]
+fn interval_0_never_promotes() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config(0));
+    panel.borrow_mut().commands.clear();
+
+    let area = Rectangle::new(Point::zero(), Size::new(8, 8));
+    for _ in 0..4 {
+        block_on(display.draw_partial(&FRAME, area)).unwrap();
+    }
+    assert_eq!(panel.borrow().full_refreshes(), [false; 4]);
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{NullDriver, SSD1681_RAM, block_on, driver, panel};
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Circle, PrimitiveStyle};
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::graphics::{BufferedDisplay, Rotation};
+use ssd1681_async::grayscale::GrayscaleDisplay;
+
+const X: usize = 32;
+const Y: usize = 32;
+const N: usize = 2 * X * Y / 8;
+
+type Display = GrayscaleDisplay<NullDriver<X, Y>, N, Gray4>;
+
+/// Gray level of an anti-aliased disc of `radius` around `center`, from the share of 4x4
+/// subsamples of each pixel that fall inside it.
+fn coverage(point: Point, center: (f32, f32), radius: f32) -> Gray4 {
+    let mut inside = 0;
+    for sy in 0..4 {
+        for sx in 0..4 {
+            let x = point.x as f32 + (sx as f32 + 0.5) / 4.0 - center.0;
+            let y = point.y as f32 + (sy as f32 + 0.5) / 4.0 - center.1;
+            if x * x + y * y <= radius * radius {
+                inside += 1;
+            }
+        }
+    }
+    Gray4::new((inside * 15 + 8) / 16)
+}
+
+#[test]
+fn anti_aliased_circle_maps_gray_levels_to_both_planes() {
+    let mut display = Display::with_driver(NullDriver, Rotation::_0);
+    let points: Vec<_> = display.bounding_box().points().collect();
+    let pixels: Vec<_> = points
+        .iter()
+        .map(|&point| Pixel(point, coverage(point, (16.0, 16.0), 10.3)))
+        .collect();
+    display.draw_iter(pixels.iter().copied()).unwrap();
+
+    assert_eq!(display.pixel(Point::new(16, 16)), Some(Gray2::WHITE));
+    assert_eq!(display.pixel(Point::new(0, 0)), Some(Gray2::BLACK));
+    for &Pixel(point, color) in &pixels {
+        assert_eq!(display.pixel(point), Some(Gray2::from(color)), "{point:?}");
+    }
+
+    // the rim is drawn in the two middle levels, one bit set in each
+    let levels: Vec<_> = pixels
+        .iter()
+        .filter_map(|&Pixel(point, _)| display.pixel(point))
+        .map(|level| level.luma())
+        .collect();
+    assert!(levels.contains(&1));
+    assert!(levels.contains(&2));
+
+    // the center pixel sets the same bit in the high and the low plane
+    let (high, low) = display.buffer().split_at(N / 2);
+    let byte = 16 * X / 8 + 16 / 8;
+    assert_eq!(high[byte] & 0x80, 0x80);
+    assert_eq!(low[byte] & 0x80, 0x80);
+}
+
+#[test]
+fn thick_strokes_keep_their_width_and_level() {
+    let circle = Circle::new(Point::new(4, 4), 24);
+
+    let mut display = Display::with_driver(NullDriver, Rotation::_0);
+    circle
+        .into_styled(PrimitiveStyle::with_stroke(Gray4::new(9), 3))
+        .draw(&mut display)
+        .unwrap();
+
+    let mut reference =
+        BufferedDisplay::<_, { N / 2 }>::with_driver(NullDriver::<X, Y>, Rotation::_0);
+    circle
+        .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 3))
+        .draw(&mut reference)
+        .unwrap();
+
+    for point in display.bounding_box().points() {
+        let expected = match reference.pixel(point) {
+            Some(BinaryColor::On) => Gray2::new(2),
+            _ => Gray2::BLACK,
+        };
+        assert_eq!(display.pixel(point), Some(expected), "{point:?}");
+    }
+}
+
+#[test]
+fn flush_writes_the_high_bits_to_black_white_and_the_low_bits_to_red() {
+    let panel = panel(SSD1681_RAM);
+    let driver = driver(&panel, Ssd1681Config::<200, 200>::default());
+    let mut display = GrayscaleDisplay::<_, 10000>::with_driver(driver, Rotation::_0);
+
+    for (x, level) in [(0, 0), (1, 1), (2, 2), (3, 3)] {
+        Pixel(Point::new(x, 0), Gray2::new(level))
+            .draw(&mut display)
+            .unwrap();
+    }
+    block_on(display.flush()).unwrap();
+
+    let state = panel.borrow();
+    assert_eq!(state.ram(false, 0, 0), 0b0011_0000);
+    assert_eq!(state.ram(true, 0, 0), 0b0101_0000);
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::{PanelDriver, SSD1681_RAM, block_on, driver, panel};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::graphics::Rotation;
+use ssd1681_async::hybrid::HybridDisplay;
+use ssd1681_async::tricolor::TriColor;
+
+const N: usize = 2 * 200 * 200 / 8;
+
+fn fill(display: &mut HybridDisplay<PanelDriver<200, 200>, N>, area: Rectangle, color: TriColor) {
+    area.into_styled(PrimitiveStyle::with_fill(color))
+        .draw(display)
+        .unwrap();
+}
+
+/// A display whose panel shows a red banner over the top 16 lines, with the log cleared.
+fn with_red_banner() -> (common::Panel, HybridDisplay<PanelDriver<200, 200>, N>) {
+    let panel = panel(SSD1681_RAM);
+    let driver = driver(&panel, Ssd1681Config::default());
+    let mut display = HybridDisplay::<_, N>::with_driver(driver, Rotation::_0);
+
+    fill(
+        &mut display,
+        Rectangle::new(Point::zero(), Size::new(200, 16)),
+        TriColor::Red,
+    );
+    block_on(display.flush()).unwrap();
+    panel.borrow_mut().commands.clear();
+    (panel, display)
+}
+
+#[test]
+fn mono_flushes_leave_red_ram_untouched() {
+    let (panel, mut display) = with_red_banner();
+    let red = panel.borrow().red.clone();
+
+    for x in [40, 80, 120] {
+        fill(
+            &mut display,
+            Rectangle::new(Point::new(x, 100), Size::new(16, 16)),
+            TriColor::White,
+        );
+        assert!(!display.needs_full_refresh());
+        block_on(display.flush()).unwrap();
+    }
+
+    let state = panel.borrow();
+    assert!(state.sent(0x26).is_empty());
+    assert_eq!(state.red, red);
+    assert_eq!(state.ram(false, 5, 100), 0xff);
+    // partial updates only, no full refresh sequence
+    assert!(state.sent(0x22).iter().all(|data| data[0] & 0x08 != 0));
+}
+
+#[test]
+fn a_red_change_forces_a_full_refresh() {
+    let (panel, mut display) = with_red_banner();
+
+    fill(
+        &mut display,
+        Rectangle::new(Point::new(0, 184), Size::new(200, 16)),
+        TriColor::Red,
+    );
+    assert!(display.needs_full_refresh());
+    block_on(display.flush()).unwrap();
+
+    let state = panel.borrow();
+    assert_eq!(state.sent(0x26).len(), 1);
+    assert_eq!(state.ram(true, 0, 190), 0xff);
+    assert_eq!(state.ram(true, 0, 8), 0xff);
+    assert!(state.sent(0x22).iter().all(|data| data[0] & 0x08 == 0));
+    assert!(!display.needs_full_refresh());
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use common::{SSD1681_RAM, panel, try_driver};
+use ssd1681_async::config::{BusyStrategy, Ssd1681Config};
+use ssd1681_async::errors::DisplayError;
+
+const BASE_MS: u32 = 7;
+
+fn config() -> Ssd1681Config<200, 200> {
+    Ssd1681Config {
+        busy_strategy: BusyStrategy::Poll(5),
+        busy_timeout_ms: Some(50),
+        init_max_retries: 2,
+        init_backoff_base_ms: BASE_MS,
+        ..Ssd1681Config::default()
+    }
+}
+
+fn backoffs(panel: &common::Panel) -> Vec<u32> {
+    let state = panel.borrow();
+    state
+        .delays_ms
+        .iter()
+        .copied()
+        .filter(|ms| [BASE_MS, 2 * BASE_MS].contains(ms))
+        .collect()
+}
+
+#[test]
+fn a_slow_cold_panel_is_retried_with_backoff() {
+    let panel = panel(SSD1681_RAM);
+    // busy through the first attempt's 50 ms timeout, idle a few polls into the second
+    panel.borrow_mut().busy_high_reads = 14;
+
+    try_driver(&panel, config()).unwrap();
+
+    assert_eq!(backoffs(&panel), [BASE_MS]);
+    assert_eq!(panel.borrow().sent(0x12).len(), 1);
+}
+
+#[test]
+fn a_stuck_busy_line_times_out_after_every_retry() {
+    let panel = panel(SSD1681_RAM);
+    panel.borrow_mut().busy_high_reads = u32::MAX;
+
+    let init = try_driver(&panel, config());
+
+    assert!(matches!(init, Err(DisplayError::BusyTimeout)));
+    assert_eq!(backoffs(&panel), [BASE_MS, 2 * BASE_MS]);
+    assert!(panel.borrow().sent(0x12).is_empty());
+}
+
+#[test]
+fn polling_without_an_interval_still_times_out() {
+    let panel = panel(SSD1681_RAM);
+    panel.borrow_mut().busy_high_reads = u32::MAX;
+
+    let init = try_driver(
+        &panel,
+        Ssd1681Config::<200, 200> {
+            busy_strategy: BusyStrategy::Poll(0),
+            ..config()
+        },
+    );
+
+    assert!(matches!(init, Err(DisplayError::BusyTimeout)));
+}
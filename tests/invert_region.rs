@@ -0,0 +1,51 @@
+mod common;
+
+use common::NullDriver;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use ssd1681_async::graphics::{BufferedDisplay, Rotation};
+
+const X: usize = 200;
+const Y: usize = 104;
+const N: usize = X * Y / 8;
+
+fn background(x: u32, y: u32) -> bool {
+    (x * 7 + y * 13) % 5 < 2
+}
+
+fn display(rotation: Rotation, invert: Option<Rectangle>) -> BufferedDisplay<NullDriver<X, Y>, N> {
+    let mut display = BufferedDisplay::<_, N>::with_driver(NullDriver::<X, Y>, rotation);
+    display.fill_with(|x, y| {
+        let inverted = invert.is_some_and(|area| area.contains(Point::new(x as i32, y as i32)));
+        BinaryColor::from(background(x, y) != inverted)
+    });
+    display
+}
+
+#[test]
+fn only_pixels_inside_an_unaligned_rectangle_are_inverted() {
+    // x edges 3 and 42 fall inside buffer bytes under _0; under _90 y edges 11 and 29 do
+    let area = Rectangle::with_corners(Point::new(3, 11), Point::new(42, 29));
+
+    for rotation in [Rotation::_0, Rotation::_90] {
+        let mut inverted = display(rotation, None);
+        inverted.invert_region(area);
+
+        assert!(inverted.buffer() == display(rotation, Some(area)).buffer());
+    }
+}
+
+#[test]
+fn inverting_twice_restores_the_buffer() {
+    let area = Rectangle::with_corners(Point::new(5, 1), Point::new(12, 98));
+
+    for rotation in [Rotation::_0, Rotation::_90] {
+        let mut display = display(rotation, None);
+        let before = *display.buffer();
+        display.invert_region(area);
+        display.invert_region(area);
+
+        assert!(display.buffer() == &before);
+    }
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::UpdateMode;
+use ssd1681_async::errors::DisplayError;
+
+const FRAME: [u8; 5000] = [0xff; 5000];
+const SHORT_FRAME: [u8; 10] = [0xff; 10];
+
+#[test]
+fn a_failed_frame_still_powers_down() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    panel.borrow_mut().commands.clear();
+
+    let frames: [&[u8]; 2] = [&FRAME, &SHORT_FRAME];
+    let played = block_on(display.play(frames, UpdateMode::Partial));
+
+    assert!(matches!(played, Err(DisplayError::BufferSizeMismatch)));
+    let state = panel.borrow();
+    let tail: Vec<_> = state.commands.iter().rev().take(2).collect();
+    assert_eq!(tail[1], &(0x22, vec![0x03]));
+    assert_eq!(tail[0].0, 0x20);
+}
+
+#[test]
+fn the_frame_error_wins_over_a_failed_power_down() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    {
+        let mut state = panel.borrow_mut();
+        state.commands.clear();
+        // the power up is the first update control write, the power down the second
+        state.fail_command = Some((0x22, 1));
+    }
+
+    let frames: [&[u8]; 1] = [&SHORT_FRAME];
+    let played = block_on(display.play(frames, UpdateMode::Partial));
+
+    assert!(matches!(played, Err(DisplayError::BufferSizeMismatch)));
+    assert_eq!(panel.borrow().sent(0x22).len(), 2);
+}
+
+#[test]
+fn a_failed_power_down_is_reported_after_good_frames() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    {
+        let mut state = panel.borrow_mut();
+        state.commands.clear();
+        state.fail_command = Some((0x22, 2));
+    }
+
+    let frames: [&[u8]; 1] = [&FRAME];
+    let played = block_on(display.play(frames, UpdateMode::Partial));
+
+    assert!(matches!(played, Err(DisplayError::SpiFailure)));
+}
+
+#[test]
+fn play_refuses_below_the_minimum_temperature() {
+    let panel = panel(SSD1681_RAM);
+    let config = Ssd1681Config::<200, 200> {
+        // the simulated sensor reads 0 °C
+        min_refresh_temp_c: Some(5),
+        ..Default::default()
+    };
+    let mut display = driver(&panel, config);
+    panel.borrow_mut().commands.clear();
+
+    let frames: [&[u8]; 1] = [&FRAME];
+    let played = block_on(display.play(frames, UpdateMode::Partial));
+
+    assert!(matches!(
+        played,
+        Err(DisplayError::TooCold { temp: 0, min: 5 })
+    ));
+    assert!(panel.borrow().sent(0x24).is_empty());
+}
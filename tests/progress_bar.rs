@@ -0,0 +1,70 @@
+mod common;
+
+use common::NullDriver;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use ssd1681_async::graphics::{BufferedDisplay, Rotation};
+use ssd1681_async::widgets::ProgressBar;
+
+const X: usize = 16;
+const Y: usize = 8;
+const N: usize = X * Y / 8;
+
+fn setup() -> (BufferedDisplay<NullDriver<X, Y>, N>, ProgressBar) {
+    let display = BufferedDisplay::with_driver(NullDriver::<X, Y>, Rotation::_0);
+    let area = Rectangle::new(Point::new(2, 2), Size::new(10, 4));
+    (
+        display,
+        ProgressBar::new(area, BinaryColor::On, BinaryColor::Off),
+    )
+}
+
+fn filled_columns(display: &BufferedDisplay<NullDriver<X, Y>, N>) -> Vec<i32> {
+    (0..X as i32)
+        .filter(|&x| display.pixel(Point::new(x, 3)) == Some(BinaryColor::On))
+        .collect()
+}
+
+#[test]
+fn first_update_covers_the_whole_bar() {
+    let (mut display, mut bar) = setup();
+
+    assert_eq!(bar.draw(&mut display, 50), Some(bar.bounding_box()));
+    assert_eq!(filled_columns(&display), vec![2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn growing_only_changes_the_new_strip() {
+    let (mut display, mut bar) = setup();
+    bar.draw(&mut display, 30);
+
+    let changed = bar.draw(&mut display, 70);
+    assert_eq!(
+        changed,
+        Some(Rectangle::new(Point::new(5, 2), Size::new(4, 4)))
+    );
+    assert_eq!(filled_columns(&display), vec![2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+fn shrinking_clears_the_dropped_strip() {
+    let (mut display, mut bar) = setup();
+    bar.draw(&mut display, 100);
+
+    let changed = bar.draw(&mut display, 20);
+    assert_eq!(
+        changed,
+        Some(Rectangle::new(Point::new(4, 2), Size::new(8, 4)))
+    );
+    assert_eq!(filled_columns(&display), vec![2, 3]);
+}
+
+#[test]
+fn unchanged_fill_needs_no_refresh() {
+    let (mut display, mut bar) = setup();
+    bar.draw(&mut display, 42);
+
+    assert_eq!(bar.draw(&mut display, 45), None);
+    assert_eq!(bar.value(), Some(45));
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::block_on;
+use ssd1681_async::config::{ScanMode, Ssd1681Config};
+use ssd1681_async::record::{CommandSink, record_init};
+
+#[derive(Default)]
+struct Log {
+    commands: Vec<(u8, Vec<u8>)>,
+}
+
+impl CommandSink for Log {
+    fn command(&mut self, command: u8) {
+        self.commands.push((command, Vec::new()));
+    }
+
+    fn data(&mut self, data: &[u8]) {
+        if let Some((_, buffer)) = self.commands.last_mut() {
+            buffer.extend_from_slice(data);
+        }
+    }
+}
+
+fn record(config: Ssd1681Config<200, 200>) -> Vec<(u8, Vec<u8>)> {
+    let mut log = Log::default();
+    block_on(record_init(config, &mut log)).unwrap();
+    log.commands
+}
+
+#[test]
+fn starts_with_software_reset_and_window_setup() {
+    let commands = record(Ssd1681Config::default());
+
+    assert_eq!(commands[0], (0x12, vec![]));
+    assert_eq!(commands[1], (0x11, vec![0x03]));
+    assert_eq!(commands[2], (0x44, vec![0, 24]));
+    assert_eq!(commands[3], (0x45, vec![0, 0, 199, 0]));
+}
+
+#[test]
+fn clearing_writes_both_ram_banks_and_refreshes() {
+    let commands = record(Ssd1681Config::default());
+
+    let ram_writes: Vec<_> = commands
+        .iter()
+        .filter(|(command, _)| matches!(command, 0x24 | 0x26))
+        .collect();
+    assert_eq!(ram_writes.len(), 2);
+    assert!(ram_writes.iter().all(|(_, data)| data.len() == 5000));
+    assert_eq!(commands.last(), Some(&(0x20, vec![])));
+}
+
+#[test]
+fn without_clearing_no_ram_is_written() {
+    let commands = record(Ssd1681Config {
+        clear_on_init: false,
+        ..Ssd1681Config::default()
+    });
+
+    assert!(!commands.iter().any(|(command, _)| *command == 0x24));
+}
+
+fn driver_output_control(config: Ssd1681Config<200, 200>) -> Vec<u8> {
+    record(config)
+        .into_iter()
+        .find(|(command, _)| *command == 0x01)
+        .map(|(_, data)| data)
+        .unwrap()
+}
+
+#[test]
+fn reverse_gate_scan_sets_the_tb_bit() {
+    let data = driver_output_control(Ssd1681Config {
+        reverse_gate_scan: true,
+        ..Ssd1681Config::default()
+    });
+
+    assert_eq!(data, vec![199, 0, 0x01]);
+}
+
+#[test]
+fn reverse_gate_scan_combines_with_interlacing() {
+    let data = driver_output_control(Ssd1681Config {
+        reverse_gate_scan: true,
+        scan_mode: ScanMode::Interlaced,
+        ..Ssd1681Config::default()
+    });
+
+    assert_eq!(data, vec![199, 0, 0x03]);
+}
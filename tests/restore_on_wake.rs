@@ -0,0 +1,91 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::DisplayDriver;
+
+const LEN: usize = 5000;
+const BLACK_WHITE: [u8; LEN] = [0xf0; LEN];
+const RED: [u8; LEN] = [0x0c; LEN];
+
+/// Puts the controller to sleep and wakes it, losing RAM in between like the real one.
+fn sleep_and_wake(panel: &common::Panel, display: &mut common::PanelDriver<200, 200>) {
+    block_on(display.sleep()).unwrap();
+    {
+        let mut state = panel.borrow_mut();
+        state.black_white.fill(0x55);
+        state.red.fill(0x55);
+    }
+    block_on(display.wake()).unwrap();
+}
+
+fn banks(panel: &common::Panel) -> (Vec<u8>, Vec<u8>) {
+    let state = panel.borrow();
+    (state.black_white.clone(), state.red.clone())
+}
+
+#[test]
+fn the_last_frame_is_restored_to_both_banks() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    display.retain_frames_in(vec![0; LEN].leak());
+    block_on(display.draw_frame(&BLACK_WHITE)).unwrap();
+
+    sleep_and_wake(&panel, &mut display);
+
+    assert_eq!(banks(&panel), (BLACK_WHITE.to_vec(), BLACK_WHITE.to_vec()));
+}
+
+#[test]
+fn nothing_is_restored_without_restore_ram_on_wake() {
+    let panel = panel(SSD1681_RAM);
+    let config = Ssd1681Config::<200, 200> {
+        restore_ram_on_wake: false,
+        ..Ssd1681Config::default()
+    };
+    let mut display = driver(&panel, config);
+    display.retain_frames_in(vec![0; LEN].leak());
+    block_on(display.draw_frame(&BLACK_WHITE)).unwrap();
+
+    sleep_and_wake(&panel, &mut display);
+
+    assert_eq!(banks(&panel), (vec![0x55; LEN], vec![0x55; LEN]));
+}
+
+#[test]
+fn tri_color_frames_restore_their_red_plane_given_room() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    display.retain_frames_in(vec![0; 2 * LEN].leak());
+    block_on(display.draw_tri_color_frame(&BLACK_WHITE, &RED)).unwrap();
+
+    sleep_and_wake(&panel, &mut display);
+
+    assert_eq!(banks(&panel), (BLACK_WHITE.to_vec(), RED.to_vec()));
+}
+
+#[test]
+fn tri_color_frames_clear_red_without_room() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    display.retain_frames_in(vec![0; LEN].leak());
+    block_on(display.draw_tri_color_frame(&BLACK_WHITE, &RED)).unwrap();
+
+    sleep_and_wake(&panel, &mut display);
+
+    // rather than turning every white pixel red with a copy of the black/white plane
+    assert_eq!(banks(&panel), (BLACK_WHITE.to_vec(), vec![0x00; LEN]));
+}
+
+#[test]
+fn a_black_white_frame_after_a_tri_color_one_mirrors_again() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    display.retain_frames_in(vec![0; 2 * LEN].leak());
+    block_on(display.draw_tri_color_frame(&RED, &RED)).unwrap();
+    block_on(display.draw_frame(&BLACK_WHITE)).unwrap();
+
+    sleep_and_wake(&panel, &mut display);
+
+    assert_eq!(banks(&panel), (BLACK_WHITE.to_vec(), BLACK_WHITE.to_vec()));
+}
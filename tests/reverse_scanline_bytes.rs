@@ -0,0 +1,92 @@
+mod common;
+
+use common::{SSD1680_RAM, SSD1681_RAM, block_on, driver, panel};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::DisplayDriver;
+
+fn config() -> Ssd1681Config<200, 200> {
+    Ssd1681Config {
+        reverse_scanline_bytes: true,
+        ..Default::default()
+    }
+}
+
+/// A frame whose every byte is distinct from its neighbours, so misplaced bytes show.
+fn frame() -> Vec<u8> {
+    (0..5000).map(|index| (index * 7 % 251) as u8).collect()
+}
+
+#[test]
+fn full_frames_land_mirrored_in_ram() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config());
+    let frame = frame();
+
+    block_on(display.draw_frame(&frame)).unwrap();
+
+    let state = panel.borrow();
+    for y in 0..200 {
+        for column in 0..25 {
+            assert_eq!(state.ram(false, 24 - column, y), frame[y * 25 + column]);
+        }
+    }
+}
+
+#[test]
+fn partial_windows_off_column_0_land_mirrored_in_ram() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config());
+    let frame = frame();
+    panel.borrow_mut().commands.clear();
+
+    // buffer columns 2..=5 of lines 10..=29, so RAM columns 19..=22
+    let area = Rectangle::new(Point::new(16, 10), Size::new(32, 20));
+    block_on(display.draw_partial(&frame, area)).unwrap();
+
+    let state = panel.borrow();
+    assert_eq!(state.sent(0x44)[0], &[19, 22][..]);
+    for y in 0..200 {
+        for column in 0..25 {
+            let inside = (2..=5).contains(&column) && (10..30).contains(&y);
+            // init cleared the RAM to white
+            let expected = if inside { frame[y * 25 + column] } else { 0xff };
+            assert_eq!(state.ram(false, 24 - column, y), expected, "{column}, {y}");
+            if inside {
+                assert_eq!(
+                    state.ram(true, 24 - column, y),
+                    expected,
+                    "red {column}, {y}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn self_test_patterns_are_sent_with_reversed_scanlines() {
+    let panel = panel(SSD1680_RAM);
+    let config = Ssd1681Config::<128, 296> {
+        reverse_scanline_bytes: true,
+        ..Default::default()
+    };
+    let mut display = driver(&panel, config);
+    panel.borrow_mut().commands.clear();
+
+    block_on(display.self_test(0)).unwrap();
+
+    // 16 byte columns, so the checkerboard isn't symmetric across a scanline
+    let checkerboard: Vec<u8> = (0..16 * 296)
+        .map(|index| {
+            let (column, row) = (15 - index % 16, index / 16);
+            if (column + row / 8) % 2 == 0 {
+                0x00
+            } else {
+                0xff
+            }
+        })
+        .collect();
+    let state = panel.borrow();
+    assert_eq!(state.sent(0x24)[2], checkerboard.as_slice());
+}
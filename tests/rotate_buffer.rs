@@ -0,0 +1,81 @@
+use ssd1681_async::graphics::{Rotation, rotate_buffer};
+
+// 16x8 panel with a single asymmetric mark: an L shape in the logical top-left corner, three
+// pixels tall and two wide, so every rotation lands it in a distinct place.
+const X: usize = 16;
+const Y: usize = 8;
+
+fn is_set(buffer: &[u8], bytes_per_row: usize, x: usize, y: usize) -> bool {
+    buffer[y * bytes_per_row + x / 8] & (0b1000_0000 >> (x % 8)) != 0
+}
+
+fn set(buffer: &mut [u8], bytes_per_row: usize, x: usize, y: usize) {
+    buffer[y * bytes_per_row + x / 8] |= 0b1000_0000 >> (x % 8);
+}
+
+fn l_shape(width: usize, height: usize) -> [u8; 16] {
+    let mut src = [0u8; 16];
+    let bytes_per_row = width.div_ceil(8);
+    assert!(bytes_per_row * height <= src.len());
+    for (x, y) in [(0, 0), (0, 1), (0, 2), (1, 2)] {
+        set(&mut src, bytes_per_row, x, y);
+    }
+    src
+}
+
+fn set_pixels(dst: &[u8]) -> Vec<(usize, usize)> {
+    let mut pixels = Vec::new();
+    for y in 0..Y {
+        for x in 0..X {
+            if is_set(dst, X / 8, x, y) {
+                pixels.push((x, y));
+            }
+        }
+    }
+    pixels
+}
+
+#[test]
+fn rotate_0_copies_image() {
+    let src = l_shape(X, Y);
+    let mut dst = [0u8; X * Y / 8];
+    rotate_buffer::<X, Y>(&src, Rotation::_0, &mut dst);
+
+    assert_eq!(set_pixels(&dst), [(0, 0), (0, 1), (0, 2), (1, 2)]);
+}
+
+#[test]
+fn rotate_90() {
+    let src = l_shape(Y, X);
+    let mut dst = [0u8; X * Y / 8];
+    rotate_buffer::<X, Y>(&src, Rotation::_90, &mut dst);
+
+    assert_eq!(set_pixels(&dst), [(13, 0), (14, 0), (15, 0), (13, 1)]);
+}
+
+#[test]
+fn rotate_180() {
+    let src = l_shape(X, Y);
+    let mut dst = [0u8; X * Y / 8];
+    rotate_buffer::<X, Y>(&src, Rotation::_180, &mut dst);
+
+    assert_eq!(set_pixels(&dst), [(14, 5), (15, 5), (15, 6), (15, 7)]);
+}
+
+#[test]
+fn rotate_270() {
+    let src = l_shape(Y, X);
+    let mut dst = [0u8; X * Y / 8];
+    rotate_buffer::<X, Y>(&src, Rotation::_270, &mut dst);
+
+    assert_eq!(set_pixels(&dst), [(2, 6), (0, 7), (1, 7), (2, 7)]);
+}
+
+#[test]
+fn overwrites_previous_contents() {
+    let src = [0u8; X * Y / 8];
+    let mut dst = [0xFFu8; X * Y / 8];
+    rotate_buffer::<X, Y>(&src, Rotation::_180, &mut dst);
+
+    assert_eq!(dst, [0u8; X * Y / 8]);
+}
@@ -0,0 +1,181 @@
+mod common;
+
+use common::block_on;
+use core::cell::RefCell;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use ssd1681_async::driver::DisplayDriver;
+use ssd1681_async::graphics::{BufferedDisplay, Rotation};
+use std::rc::Rc;
+
+// a non-square panel, so a transposed mapping can't pass by accident
+const X: usize = 16;
+const Y: usize = 8;
+const N: usize = X * Y / 8;
+
+/// Driver that keeps the last frame it was asked to draw.
+#[derive(Default)]
+struct CaptureDriver {
+    frame: Rc<RefCell<Vec<u8>>>,
+}
+
+impl DisplayDriver for CaptureDriver {
+    type Error = ();
+
+    const X: usize = X;
+    const Y: usize = Y;
+
+    async fn draw_frame(&mut self, buffer: &[u8]) -> Result<(), ()> {
+        *self.frame.borrow_mut() = buffer.to_vec();
+        Ok(())
+    }
+
+    async fn draw_partial(&mut self, buffer: &[u8], _area: Rectangle) -> Result<(), ()> {
+        *self.frame.borrow_mut() = buffer.to_vec();
+        Ok(())
+    }
+
+    async fn draw_tri_color_frame(&mut self, black_white: &[u8], _red: &[u8]) -> Result<(), ()> {
+        *self.frame.borrow_mut() = black_white.to_vec();
+        Ok(())
+    }
+
+    async fn enable_backlight(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn disable_backlight(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+/// Draws a single pixel and returns the flushed frame.
+fn render(rotation: Rotation, point: Point) -> [u8; N] {
+    let driver = CaptureDriver::default();
+    let frame = driver.frame.clone();
+
+    let mut display = BufferedDisplay::<_, N>::with_driver(driver, rotation);
+    Pixel(point, BinaryColor::On).draw(&mut display).unwrap();
+    block_on(display.flush()).unwrap();
+
+    frame.borrow().as_slice().try_into().unwrap()
+}
+
+/// Asserts exactly one bit is set in `frame`, at `hw_x`, `hw_y`.
+fn assert_single_bit(frame: &[u8; N], hw_x: usize, hw_y: usize) {
+    let byte_index = hw_y * (X / 8) + hw_x / 8;
+    let bit_index = hw_x % 8;
+
+    for (index, byte) in frame.iter().enumerate() {
+        if index == byte_index {
+            assert_eq!(*byte, 0b1000_0000 >> bit_index, "byte {index}");
+        } else {
+            assert_eq!(*byte, 0, "byte {index}");
+        }
+    }
+}
+
+fn assert_corners(rotation: Rotation, expected: [(usize, usize); 4]) {
+    let size = BufferedDisplay::<_, N>::with_driver(CaptureDriver::default(), rotation)
+        .bounding_box()
+        .size;
+    let (w, h) = (size.width as i32, size.height as i32);
+    let corners = [
+        Point::new(0, 0),
+        Point::new(w - 1, 0),
+        Point::new(0, h - 1),
+        Point::new(w - 1, h - 1),
+    ];
+
+    for (corner, (hw_x, hw_y)) in corners.into_iter().zip(expected) {
+        assert_single_bit(&render(rotation, corner), hw_x, hw_y);
+    }
+}
+
+#[test]
+fn rotation_0_corners() {
+    assert_corners(Rotation::_0, [(0, 0), (15, 0), (0, 7), (15, 7)]);
+}
+
+#[test]
+fn rotation_90_corners() {
+    assert_corners(Rotation::_90, [(15, 0), (15, 7), (0, 0), (0, 7)]);
+}
+
+#[test]
+fn rotation_180_corners() {
+    assert_corners(Rotation::_180, [(15, 7), (0, 7), (15, 0), (0, 0)]);
+}
+
+#[test]
+fn rotation_270_corners() {
+    assert_corners(Rotation::_270, [(0, 7), (0, 0), (15, 7), (15, 0)]);
+}
+
+#[test]
+fn out_of_bounds_pixels_are_ignored() {
+    for rotation in [Rotation::_0, Rotation::_90, Rotation::_180, Rotation::_270] {
+        for point in [
+            Point::new(-1, 0),
+            Point::new(0, -1),
+            Point::new(16, 0),
+            Point::new(0, 16),
+        ] {
+            assert_eq!(render(rotation, point), [0; N]);
+        }
+    }
+}
+
+#[test]
+fn flush_rotated_180_matches_drawing_at_180() {
+    let shape = [
+        Point::new(0, 0),
+        Point::new(0, 1),
+        Point::new(1, 1),
+        Point::new(9, 5),
+    ];
+
+    let rotated = {
+        let driver = CaptureDriver::default();
+        let frame = driver.frame.clone();
+        let mut display = BufferedDisplay::<_, N>::with_driver(driver, Rotation::_180);
+        for point in shape {
+            Pixel(point, BinaryColor::On).draw(&mut display).unwrap();
+        }
+        block_on(display.flush()).unwrap();
+        frame.borrow().clone()
+    };
+
+    let driver = CaptureDriver::default();
+    let frame = driver.frame.clone();
+    let mut display = BufferedDisplay::<_, N>::with_driver(driver, Rotation::_0);
+    for point in shape {
+        Pixel(point, BinaryColor::On).draw(&mut display).unwrap();
+    }
+    let unrotated = *display.buffer();
+    block_on(display.flush_rotated_180()).unwrap();
+
+    assert_eq!(*frame.borrow(), rotated);
+    assert_eq!(*display.buffer(), unrotated);
+}
+
+#[test]
+fn fill_with_matches_drawing_every_pixel() {
+    let pattern = |x: u32, y: u32| BinaryColor::from((x * 3 + y * y) % 5 < 2);
+
+    for rotation in [Rotation::_0, Rotation::_90, Rotation::_180, Rotation::_270] {
+        let mut expected = BufferedDisplay::<_, N>::with_driver(CaptureDriver::default(), rotation);
+        let points: Vec<_> = expected.bounding_box().points().collect();
+        for point in points {
+            Pixel(point, pattern(point.x as u32, point.y as u32))
+                .draw(&mut expected)
+                .unwrap();
+        }
+
+        let mut display = BufferedDisplay::<_, N>::with_driver(CaptureDriver::default(), rotation);
+        display.fill_with(pattern);
+
+        assert_eq!(display.buffer(), expected.buffer());
+    }
+}
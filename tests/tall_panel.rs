@@ -0,0 +1,42 @@
+mod common;
+
+use common::{block_on, driver, panel};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::DisplayDriver;
+
+// 296 lines, so gate addresses past 255 need bit 8 in the second byte
+const RAM: (usize, usize) = (25, 296);
+
+#[test]
+fn the_full_window_ends_on_line_295() {
+    let panel = panel(RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 296>::default());
+    panel.borrow_mut().commands.clear();
+
+    block_on(display.draw_frame(&[0xff; 25 * 296])).unwrap();
+
+    let state = panel.borrow();
+    assert_eq!(state.sent(0x45).last(), Some(&&[0, 0, 39, 1][..]));
+    assert_eq!(state.ram(false, 24, 295), 0xff);
+}
+
+#[test]
+fn a_window_below_line_255_sets_bit_8_of_the_pointer() {
+    let panel = panel(RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 296>::default());
+    let mut frame = [0x00; 25 * 296];
+    frame[290 * 25..291 * 25].fill(0xa5);
+    panel.borrow_mut().commands.clear();
+
+    let area = Rectangle::new(Point::new(0, 288), Size::new(200, 8));
+    block_on(display.draw_partial(&frame, area)).unwrap();
+
+    let state = panel.borrow();
+    let pointer = state.sent(0x4f)[0];
+    assert_eq!(pointer, &[32, 1][..]);
+    assert_eq!(state.ram(false, 0, 290), 0xa5);
+    assert_eq!(state.ram(false, 24, 290), 0xa5);
+    assert_eq!(state.ram(false, 0, 289), 0x00);
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "trace")]
+
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use core::cell::RefCell;
+use ssd1681_async::config::{CommandTrace, Ssd1681Config};
+use ssd1681_async::driver::DisplayDriver;
+
+std::thread_local! {
+    static TRACED: RefCell<Vec<CommandTrace>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(trace: CommandTrace) {
+    TRACED.with_borrow_mut(|traced| traced.push(trace));
+}
+
+#[test]
+fn every_command_of_a_frame_is_traced() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(
+        &panel,
+        Ssd1681Config::<200, 200> {
+            trace: Some(record),
+            ..Ssd1681Config::default()
+        },
+    );
+    panel.borrow_mut().commands.clear();
+    TRACED.with_borrow_mut(Vec::clear);
+
+    block_on(display.draw_frame(&[0xff; 5000])).unwrap();
+
+    let traced: Vec<_> = TRACED.with_borrow(|traced| traced.iter().map(|t| t.command).collect());
+    let sent: Vec<_> = panel.borrow().commands.iter().map(|(c, _)| *c).collect();
+    assert_eq!(traced, sent);
+    assert!(traced.contains(&0x24));
+    assert!(traced.contains(&0x20));
+
+    let write = TRACED.with_borrow(|traced| *traced.iter().find(|t| t.command == 0x24).unwrap());
+    assert_eq!(write.data_len, 5000);
+    assert_eq!(write.name, "WRITE_RAM");
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::DisplayDriver;
+
+const FROM: [u8; 5000] = [0x00; 5000];
+const TO: [u8; 5000] = [0xff; 5000];
+
+#[test]
+fn transitions_bypass_full_refresh_promotion() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(
+        &panel,
+        Ssd1681Config::<200, 200> {
+            full_refresh_interval: 2,
+            ..Ssd1681Config::default()
+        },
+    );
+    panel.borrow_mut().commands.clear();
+
+    block_on(display.transition(&FROM, &TO, 5)).unwrap();
+
+    assert_eq!(panel.borrow().full_refreshes(), [false; 5]);
+    assert!(panel.borrow().black_white.iter().all(|&byte| byte == 0xff));
+
+    // the steps weren't counted: the first partial update after them stays partial, and only
+    // the second is promoted
+    let area = Rectangle::new(Point::zero(), Size::new(8, 8));
+    for promoted in [false, true] {
+        panel.borrow_mut().commands.clear();
+        block_on(display.draw_partial(&TO, area)).unwrap();
+        assert_eq!(panel.borrow().full_refreshes(), [promoted]);
+    }
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::DisplayDriver;
+
+#[test]
+fn black_white_frames_keep_the_red_plane() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+
+    block_on(display.draw_tri_color_frame(&[0xff; 5000], &[0xa5; 5000])).unwrap();
+    block_on(display.draw_frame(&[0x00; 5000])).unwrap();
+
+    let state = panel.borrow();
+    assert!(state.red.iter().all(|&byte| byte == 0xa5));
+    assert!(state.black_white.iter().all(|&byte| byte == 0x00));
+}
+
+#[test]
+fn keeping_the_shown_frame_is_opt_in() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(
+        &panel,
+        Ssd1681Config::<200, 200> {
+            keep_shown_frame_in_red_ram: true,
+            ..Ssd1681Config::default()
+        },
+    );
+
+    block_on(display.draw_frame(&[0x0f; 5000])).unwrap();
+
+    assert!(panel.borrow().red.iter().all(|&byte| byte == 0x0f));
+}
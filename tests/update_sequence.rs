@@ -0,0 +1,24 @@
+use ssd1681_async::driver::{UpdateMode, UpdateSequence};
+
+#[test]
+fn named_sequences_match_the_datasheet_bytes() {
+    assert_eq!(UpdateSequence::FULL.bits(), 0xf7);
+    assert_eq!(UpdateSequence::PARTIAL.bits(), 0xff);
+    assert_eq!(UpdateSequence::DISPLAY_ONLY.bits(), 0xc7);
+    assert_eq!(UpdateSequence::PRELOAD_LUT.bits(), 0xb1);
+    assert_eq!(UpdateSequence::LOAD_TEMPERATURE.bits(), 0xa1);
+    assert_eq!(UpdateSequence::POWER_ON.bits(), 0xc0);
+    assert_eq!(UpdateSequence::POWER_OFF.bits(), 0x03);
+}
+
+#[test]
+fn display_mode_2_sets_only_its_bit() {
+    let partial = UpdateSequence::DISPLAY_ONLY.with_mode(UpdateMode::Partial);
+    assert_eq!(partial.bits(), 0xcf);
+    assert_eq!(
+        partial.bits(),
+        UpdateSequence::DISPLAY_ONLY
+            .with_mode(UpdateMode::CursorBlink)
+            .bits()
+    );
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::DisplayDriver;
+use ssd1681_async::errors::DisplayError;
+
+const SHOWN: [u8; 5000] = [0xaa; 5000];
+const NEXT: [u8; 5000] = [0x55; 5000];
+
+#[test]
+fn a_frame_that_reads_back_on_the_second_write_is_shown_and_retained() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    display.retain_frames_in(vec![0; 5000].leak());
+    {
+        let mut state = panel.borrow_mut();
+        state.commands.clear();
+        state.corrupt_reads = 1;
+    }
+
+    block_on(display.draw_frame_verified(&NEXT)).unwrap();
+
+    assert_eq!(panel.borrow().sent(0x24).len(), 2);
+    assert_eq!(panel.borrow().sent(0x20).len(), 1);
+    // the wake restores the retained frame to the red bank as well
+    block_on(display.wake()).unwrap();
+    assert_eq!(panel.borrow().ram(true, 0, 0), 0x55);
+    assert_eq!(panel.borrow().ram(true, 24, 199), 0x55);
+}
+
+#[test]
+fn a_frame_that_never_reads_back_is_not_retained() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, Ssd1681Config::<200, 200>::default());
+    display.retain_frames_in(vec![0; 5000].leak());
+    block_on(display.draw_frame(&SHOWN)).unwrap();
+    {
+        let mut state = panel.borrow_mut();
+        state.commands.clear();
+        state.corrupt_reads = 2;
+    }
+
+    let drawn = block_on(display.draw_frame_verified(&NEXT));
+
+    assert!(matches!(drawn, Err(DisplayError::FrameMismatch)));
+    assert!(panel.borrow().sent(0x20).is_empty());
+    block_on(display.wake()).unwrap();
+    let state = panel.borrow();
+    assert_eq!(state.ram(false, 0, 0), 0xaa);
+    assert_eq!(state.ram(true, 24, 199), 0xaa);
+}
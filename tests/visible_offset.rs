@@ -0,0 +1,71 @@
+mod common;
+
+use common::{SSD1681_RAM, block_on, driver, panel};
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use ssd1681_async::config::Ssd1681Config;
+use ssd1681_async::driver::DisplayDriver;
+
+// a 176 by 192 visible area starting at column 16, line 4 of the 200 by 200 RAM
+const COLUMNS: usize = 176 / 8;
+const LINES: usize = 192;
+
+fn config() -> Ssd1681Config<176, 192> {
+    Ssd1681Config {
+        visible_x_offset: 16,
+        visible_y_offset: 4,
+        ..Ssd1681Config::default()
+    }
+}
+
+fn frame() -> Vec<u8> {
+    (0..COLUMNS * LINES)
+        .map(|index| (index % 251) as u8)
+        .collect()
+}
+
+#[test]
+fn frames_land_at_the_visible_offset() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config());
+    let frame = frame();
+    let before = panel.borrow().black_white.clone();
+    panel.borrow_mut().commands.clear();
+
+    block_on(display.draw_frame(&frame)).unwrap();
+
+    let state = panel.borrow();
+    assert_eq!(state.sent(0x44)[0], &[2, 23][..]);
+    assert_eq!(state.sent(0x45)[0], &[4, 0, 195, 0][..]);
+    assert_eq!(state.sent(0x4e)[0], &[2][..]);
+    assert_eq!(state.sent(0x4f)[0], &[4, 0][..]);
+    for y in 0..200 {
+        for x in 0..25 {
+            let visible = (2..2 + COLUMNS).contains(&x) && (4..4 + LINES).contains(&y);
+            let expected = if visible {
+                frame[(y - 4) * COLUMNS + x - 2]
+            } else {
+                before[y * 25 + x]
+            };
+            assert_eq!(state.ram(false, x, y), expected, "{x}, {y}");
+        }
+    }
+}
+
+#[test]
+fn partial_windows_are_offset_too() {
+    let panel = panel(SSD1681_RAM);
+    let mut display = driver(&panel, config());
+    let frame = frame();
+    panel.borrow_mut().commands.clear();
+
+    let area = Rectangle::new(Point::new(8, 10), Size::new(16, 2));
+    block_on(display.draw_partial(&frame, area)).unwrap();
+
+    let state = panel.borrow();
+    assert_eq!(state.sent(0x44)[0], &[3, 4][..]);
+    assert_eq!(state.sent(0x45)[0], &[14, 0, 15, 0][..]);
+    assert_eq!(state.sent(0x4f)[0], &[14, 0][..]);
+    assert_eq!(state.ram(false, 3, 14), frame[10 * COLUMNS + 1]);
+    assert_eq!(state.ram(false, 4, 15), frame[11 * COLUMNS + 2]);
+}